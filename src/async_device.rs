@@ -0,0 +1,203 @@
+//! Async, non-blocking QCicada device interface built on `tokio-serial`.
+//!
+//! Enabled via the `async` feature. Mirrors [`crate::device::QCicada`]'s surface
+//! but returns `Future`s instead of blocking the calling thread, and exposes
+//! continuous mode as a [`futures::Stream`]. Frame building and response
+//! parsing are shared with the blocking path through [`crate::protocol`], so
+//! the wire format can never drift between the two.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio_serial::SerialPortBuilderExt;
+
+use crate::protocol::*;
+use crate::types::*;
+use crate::QCicadaError;
+
+/// Async counterpart to [`crate::device::QCicada`].
+///
+/// ```no_run
+/// # async fn run() -> Result<(), qcicada::QCicadaError> {
+/// use qcicada::AsyncQCicada;
+///
+/// let mut qrng = AsyncQCicada::open(None, None).await?;
+/// let bytes = qrng.random(32).await?;
+/// println!("{:02x?}", &bytes);
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncQCicada {
+    port: tokio_serial::SerialStream,
+}
+
+impl AsyncQCicada {
+    /// Connect to a QCicada device.
+    ///
+    /// - `port`: Serial port path. If `None`, auto-discovers the first available device.
+    /// - `timeout`: Default read timeout. If `None`, uses 2 seconds.
+    pub async fn open(
+        port: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<Self, QCicadaError> {
+        let timeout = timeout.unwrap_or(Duration::from_secs(2));
+
+        let port_name = match port {
+            Some(p) => p.to_string(),
+            None => {
+                let devices = crate::serial::find_devices();
+                devices.into_iter().next().ok_or(QCicadaError::NoDevice)?
+            }
+        };
+
+        let port = tokio_serial::new(&port_name, 1_000_000)
+            .timeout(timeout)
+            .open_native_async()
+            .map_err(|e| QCicadaError::Serial(format!("Failed to open {port_name}: {e}")))?;
+
+        Ok(Self { port })
+    }
+
+    /// Read device identification (version, serial, hardware).
+    pub async fn get_info(&mut self) -> Result<DeviceInfo, QCicadaError> {
+        let data = self
+            .command(CMD_GET_INFO, None)
+            .await?
+            .ok_or(QCicadaError::Protocol("No response to GET_INFO".into()))?;
+        parse_info(&data)
+    }
+
+    /// Get `n` random bytes using one-shot mode.
+    pub async fn random(&mut self, n: u16) -> Result<Vec<u8>, QCicadaError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let frame = build_start_one_shot(n);
+        self.command(CMD_START, Some(&frame[1..]))
+            .await?
+            .ok_or(QCicadaError::Protocol("NACK on START one-shot".into()))?;
+        self.read_exact_async(n as usize).await
+    }
+
+    /// Get `n` random bytes with a 64-byte cryptographic signature.
+    pub async fn signed_read(&mut self, n: u16) -> Result<SignedRead, QCicadaError> {
+        if n == 0 {
+            return Err(QCicadaError::Protocol(
+                "signed_read requires at least 1 byte".into(),
+            ));
+        }
+        let frame = build_signed_read(n);
+        self.command(CMD_SIGNED_READ, Some(&frame[1..]))
+            .await?
+            .ok_or(QCicadaError::Protocol("NACK on SIGNED_READ".into()))?;
+
+        let total = n as usize + SIGNATURE_LEN;
+        let buf = self.read_exact_async(total).await?;
+        Ok(SignedRead {
+            data: buf[..n as usize].to_vec(),
+            signature: buf[n as usize..].to_vec(),
+        })
+    }
+
+    /// Start continuous random data generation.
+    pub async fn start_continuous(&mut self) -> Result<(), QCicadaError> {
+        let frame = build_start_continuous();
+        self.command(CMD_START, Some(&frame[1..]))
+            .await?
+            .ok_or(QCicadaError::Protocol("NACK on START continuous".into()))?;
+        Ok(())
+    }
+
+    /// Turn the active continuous-mode session into an entropy stream.
+    ///
+    /// Call [`start_continuous`](Self::start_continuous) first. Each item is a
+    /// chunk of up to `chunk_size` bytes pulled from the serial port as it
+    /// arrives; the stream ends only if the port reports end-of-file. Dropping
+    /// it closes the port; no STOP command is sent to the device.
+    pub fn continuous_stream(self, chunk_size: usize) -> ContinuousStream {
+        ContinuousStream {
+            port: self.port,
+            chunk_size,
+        }
+    }
+
+    // --- Internal protocol handling ---
+
+    async fn command(
+        &mut self,
+        cmd_code: u8,
+        payload: Option<&[u8]>,
+    ) -> Result<Option<Vec<u8>>, QCicadaError> {
+        let expected = expected_response(cmd_code)
+            .ok_or_else(|| QCicadaError::Protocol(format!("Unknown command: {cmd_code:#04x}")))?;
+
+        let frame = build_cmd(cmd_code, payload);
+        self.port
+            .write_all(&frame)
+            .await
+            .map_err(|e| QCicadaError::Serial(format!("Write failed: {e}")))?;
+
+        let resp = self.read_exact_async(1).await?;
+        if resp[0] == expected {
+            let size = payload_size(expected);
+            if size == 0 {
+                return Ok(Some(Vec::new()));
+            }
+            let payload = self.read_exact_async(size).await?;
+            Ok(Some(payload))
+        } else if resp[0] == RESP_NACK {
+            Ok(None)
+        } else {
+            Err(QCicadaError::Protocol(format!(
+                "Unexpected response byte: {:#04x}",
+                resp[0]
+            )))
+        }
+    }
+
+    async fn read_exact_async(&mut self, len: usize) -> Result<Vec<u8>, QCicadaError> {
+        let mut buf = vec![0u8; len];
+        self.port
+            .read_exact(&mut buf)
+            .await
+            .map_err(|e| QCicadaError::Serial(format!("Read failed: {e}")))?;
+        Ok(buf)
+    }
+}
+
+/// A [`Stream`] of entropy chunks from an active continuous-mode session.
+///
+/// Produced by [`AsyncQCicada::continuous_stream`].
+pub struct ContinuousStream {
+    port: tokio_serial::SerialStream,
+    chunk_size: usize,
+}
+
+impl Stream for ContinuousStream {
+    type Item = Result<Bytes, QCicadaError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut buf = vec![0u8; this.chunk_size];
+        let mut read_buf = tokio::io::ReadBuf::new(&mut buf);
+        match Pin::new(&mut this.port).poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => {
+                let n = read_buf.filled().len();
+                if n == 0 {
+                    Poll::Ready(None)
+                } else {
+                    buf.truncate(n);
+                    Poll::Ready(Some(Ok(Bytes::from(buf))))
+                }
+            }
+            Poll::Ready(Err(e)) => {
+                Poll::Ready(Some(Err(QCicadaError::Serial(format!("Read failed: {e}")))))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -6,24 +6,58 @@
 //!
 //! 1. **Certificate verification**: Confirm the device's public key is CA-signed.
 //! 2. **Signature verification**: Confirm signed-read data was produced by the device.
+//!
+//! Verification itself goes through a pluggable [`EcdsaP256Verifier`]
+//! backend, defaulting to the pure-Rust [`RustCryptoBackend`]. Callers who
+//! must route crypto through a FIPS-validated or system library can supply
+//! their own backend (or enable the `ring` feature) via the `_with_backend`
+//! variants; the free functions below are unaffected and always use the
+//! default. [`verify_signature_prehashed`], [`verify_signature_der`] and the
+//! public-key recovery functions do not take a backend and always use `p256`.
 
-use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
-use p256::EncodedPoint;
+use p256::ecdsa::{
+    signature::hazmat::PrehashVerifier, signature::Verifier, Signature, VerifyingKey,
+};
+use p256::elliptic_curve::bigint::U256;
+use p256::elliptic_curve::ops::Reduce;
+use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use p256::elliptic_curve::Field;
+use p256::elliptic_curve::PrimeField;
+use p256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar};
+use sha2::{Digest, Sha256};
 
 use crate::protocol::{build_certificate_data, CERTIFICATE_LEN, PUB_KEY_LEN};
 
-/// Verify a device certificate against a CA public key.
-///
-/// The certificate is an ECDSA-SHA256 signature over:
-/// `u16(0) || u8(hw_major) || u8(hw_minor) || u32_le(serial_int) || pub_key[64]`
+/// A swappable ECDSA P-256 signature verifier.
 ///
-/// # Arguments
-/// - `ca_pub_key`: 64 bytes (uncompressed x || y) of the CA public key.
-/// - `device_pub_key`: 64 bytes of the device's public key.
-/// - `certificate`: 64 bytes (r || s) of the CA's signature.
-/// - `hw_major`, `hw_minor`: Hardware version from `DeviceInfo.hw_info`.
-/// - `serial_int`: Numeric serial from `DeviceInfo.serial` (e.g. 217 from "QC0000000217").
-pub fn verify_certificate(
+/// `verify_signature`/`verify_certificate` delegate to [`RustCryptoBackend`]
+/// by default. Implement this trait to route verification through a
+/// different library instead — `ring` (see the `ring` feature), a system
+/// crypto library, or a FIPS-validated module.
+pub trait EcdsaP256Verifier {
+    /// Verify an ECDSA-SHA256 signature over `message`.
+    ///
+    /// - `pub_key`: 64 bytes (x || y) of the signer's uncompressed P-256 public key.
+    /// - `sig`: 64 bytes (r || s) in big-endian.
+    fn verify(&self, pub_key: &[u8; 64], message: &[u8], sig: &[u8; 64]) -> Result<bool, String>;
+}
+
+/// The default backend: pure-Rust ECDSA P-256 verification via the `p256`
+/// (RustCrypto) crate.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RustCryptoBackend;
+
+impl EcdsaP256Verifier for RustCryptoBackend {
+    fn verify(&self, pub_key: &[u8; 64], message: &[u8], sig: &[u8; 64]) -> Result<bool, String> {
+        verify_ecdsa_p256(pub_key, message, sig)
+    }
+}
+
+/// Verify a device certificate against a CA public key, using `backend` for
+/// the underlying ECDSA verification. See [`verify_certificate`] for the
+/// default-backend version.
+pub fn verify_certificate_with_backend<B: EcdsaP256Verifier>(
+    backend: &B,
     ca_pub_key: &[u8],
     device_pub_key: &[u8],
     certificate: &[u8],
@@ -54,7 +88,68 @@ pub fn verify_certificate(
     }
 
     let message = build_certificate_data(hw_major, hw_minor, serial_int, device_pub_key);
-    verify_ecdsa_p256(ca_pub_key, &message, certificate)
+    let pub_key_arr: [u8; PUB_KEY_LEN] = ca_pub_key.try_into().unwrap();
+    let sig_arr: [u8; CERTIFICATE_LEN] = certificate.try_into().unwrap();
+    backend.verify(&pub_key_arr, &message, &sig_arr)
+}
+
+/// Verify a device certificate against a CA public key.
+///
+/// The certificate is an ECDSA-SHA256 signature over:
+/// `u16(0) || u8(hw_major) || u8(hw_minor) || u32_le(serial_int) || pub_key[64]`
+///
+/// # Arguments
+/// - `ca_pub_key`: 64 bytes (uncompressed x || y) of the CA public key.
+/// - `device_pub_key`: 64 bytes of the device's public key.
+/// - `certificate`: 64 bytes (r || s) of the CA's signature.
+/// - `hw_major`, `hw_minor`: Hardware version from `DeviceInfo.hw_info`.
+/// - `serial_int`: Numeric serial from `DeviceInfo.serial` (e.g. 217 from "QC0000000217").
+pub fn verify_certificate(
+    ca_pub_key: &[u8],
+    device_pub_key: &[u8],
+    certificate: &[u8],
+    hw_major: u8,
+    hw_minor: u8,
+    serial_int: u32,
+) -> Result<bool, String> {
+    verify_certificate_with_backend(
+        &RustCryptoBackend,
+        ca_pub_key,
+        device_pub_key,
+        certificate,
+        hw_major,
+        hw_minor,
+        serial_int,
+    )
+}
+
+/// Verify an ECDSA-SHA256 signature over data using a raw P-256 public key
+/// and `backend` for the underlying ECDSA verification. See
+/// [`verify_signature`] for the default-backend version.
+pub fn verify_signature_with_backend<B: EcdsaP256Verifier>(
+    backend: &B,
+    pub_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, String> {
+    if pub_key.len() != PUB_KEY_LEN {
+        return Err(format!(
+            "Public key must be {} bytes, got {}",
+            PUB_KEY_LEN,
+            pub_key.len()
+        ));
+    }
+    if signature.len() != CERTIFICATE_LEN {
+        return Err(format!(
+            "Signature must be {} bytes, got {}",
+            CERTIFICATE_LEN,
+            signature.len()
+        ));
+    }
+
+    let pub_key_arr: [u8; PUB_KEY_LEN] = pub_key.try_into().unwrap();
+    let sig_arr: [u8; CERTIFICATE_LEN] = signature.try_into().unwrap();
+    backend.verify(&pub_key_arr, message, &sig_arr)
 }
 
 /// Verify an ECDSA-SHA256 signature over data using a raw P-256 public key.
@@ -67,6 +162,22 @@ pub fn verify_signature(
     pub_key: &[u8],
     message: &[u8],
     signature: &[u8],
+) -> Result<bool, String> {
+    verify_signature_with_backend(&RustCryptoBackend, pub_key, message, signature)
+}
+
+/// Verify an ECDSA signature against a caller-supplied 32-byte digest,
+/// skipping the internal SHA-256 hash — for callers who already have the
+/// digest on hand (as `secp256k1`'s `Message::from_digest` expects).
+///
+/// # Arguments
+/// - `pub_key`: 64 bytes (x || y) of the signer's uncompressed P-256 public key.
+/// - `digest`: 32-byte message digest that was signed.
+/// - `signature`: 64 bytes (r || s) in big-endian.
+pub fn verify_signature_prehashed(
+    pub_key: &[u8],
+    digest: &[u8; 32],
+    signature: &[u8],
 ) -> Result<bool, String> {
     if pub_key.len() != PUB_KEY_LEN {
         return Err(format!(
@@ -83,7 +194,55 @@ pub fn verify_signature(
         ));
     }
 
-    verify_ecdsa_p256(pub_key, message, signature)
+    let mut uncompressed = vec![0x04];
+    uncompressed.extend_from_slice(pub_key);
+    let point =
+        EncodedPoint::from_bytes(&uncompressed).map_err(|e| format!("Invalid point: {e}"))?;
+    let vk = VerifyingKey::from_encoded_point(&point)
+        .map_err(|e| format!("Invalid public key: {e}"))?;
+    let sig = Signature::from_slice(signature).map_err(|e| format!("Invalid signature: {e}"))?;
+
+    match vk.verify_prehash(digest, &sig) {
+        Ok(()) => Ok(true),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Verify an ECDSA-SHA256 signature delivered as ASN.1 DER (a `SEQUENCE` of
+/// two `INTEGER`s) rather than fixed-width `r || s`, as the FIDO/CTAP stack
+/// delivers signatures.
+///
+/// # Arguments
+/// - `pub_key`: 64 bytes (x || y) of the signer's uncompressed P-256 public key.
+/// - `message`: The signed data.
+/// - `der_sig`: DER-encoded ECDSA signature.
+pub fn verify_signature_der(
+    pub_key: &[u8],
+    message: &[u8],
+    der_sig: &[u8],
+) -> Result<bool, String> {
+    if pub_key.len() != PUB_KEY_LEN {
+        return Err(format!(
+            "Public key must be {} bytes, got {}",
+            PUB_KEY_LEN,
+            pub_key.len()
+        ));
+    }
+
+    let sig =
+        Signature::from_der(der_sig).map_err(|e| format!("Malformed DER signature: {e}"))?;
+
+    let mut uncompressed = vec![0x04];
+    uncompressed.extend_from_slice(pub_key);
+    let point =
+        EncodedPoint::from_bytes(&uncompressed).map_err(|e| format!("Invalid point: {e}"))?;
+    let vk = VerifyingKey::from_encoded_point(&point)
+        .map_err(|e| format!("Invalid public key: {e}"))?;
+
+    match vk.verify(message, &sig) {
+        Ok(()) => Ok(true),
+        Err(_) => Ok(false),
+    }
 }
 
 /// Internal: verify ECDSA-SHA256 with raw key/sig bytes.
@@ -109,6 +268,152 @@ fn verify_ecdsa_p256(
     }
 }
 
+/// Alternate [`EcdsaP256Verifier`] backed by `ring` rather than `p256`, for
+/// callers who must route all crypto through `ring`'s implementation (e.g. a
+/// FIPS-targeted build). Requires the `ring` feature.
+#[cfg(feature = "ring")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RingBackend;
+
+#[cfg(feature = "ring")]
+impl EcdsaP256Verifier for RingBackend {
+    fn verify(&self, pub_key: &[u8; 64], message: &[u8], sig: &[u8; 64]) -> Result<bool, String> {
+        let mut uncompressed = [0u8; 65];
+        uncompressed[0] = 0x04;
+        uncompressed[1..].copy_from_slice(pub_key);
+
+        let key = ring::signature::UnparsedPublicKey::new(
+            &ring::signature::ECDSA_P256_SHA256_FIXED,
+            &uncompressed[..],
+        );
+        match key.verify(message, sig) {
+            Ok(()) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+// --- ECDSA public-key recovery ---
+//
+// `signed_read` returns 64-byte `r || s` plus separate `get_dev_pub_key`, but
+// like secp256k1's recoverable-signature API the signer's public key can be
+// reconstructed directly from the signature, avoiding a second round trip and
+// enabling verification when the pubkey isn't cached. The device doesn't
+// transmit a recovery id, so callers typically want `recover_candidates` or
+// `recover_and_match` rather than guessing `recovery_id` themselves.
+
+/// NIST P-256 group order `n`, big-endian.
+const P256_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// `r + n` as big-endian bytes, or `None` if the sum does not fit in 256
+/// bits. A sum that fits but is not below the field prime fails the
+/// subsequent curve-point check.
+fn add_curve_order(r: &[u8; 32]) -> Option<[u8; 32]> {
+    let mut out = [0u8; 32];
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let sum = r[i] as u16 + P256_ORDER[i] as u16 + carry;
+        out[i] = (sum & 0xff) as u8;
+        carry = sum >> 8;
+    }
+    if carry != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+/// Recover the signer's uncompressed P-256 public key (64 bytes, x || y)
+/// from a message and its `r || s` signature, given an ECDSA recovery id.
+///
+/// `recovery_id` bit 0 selects the y-parity of the candidate point `R`; bit 1
+/// selects whether `R`'s x-coordinate is `r` or `r + n` (the rare case where
+/// `r` overflowed the curve order during signing). Since the device doesn't
+/// transmit a recovery id, see [`recover_candidates`] / [`recover_and_match`].
+///
+/// Rejects the identity point and signatures with `r` or `s` at or beyond the
+/// curve order (`Scalar::from_repr` only accepts canonically-reduced input).
+pub fn recover_public_key(
+    data: &[u8],
+    signature: &[u8],
+    recovery_id: u8,
+) -> Result<Vec<u8>, String> {
+    if signature.len() != CERTIFICATE_LEN {
+        return Err(format!(
+            "Signature must be {} bytes, got {}",
+            CERTIFICATE_LEN,
+            signature.len()
+        ));
+    }
+    if recovery_id > 3 {
+        return Err(format!("Recovery id must be 0..=3, got {recovery_id}"));
+    }
+
+    let r_bytes: [u8; 32] = signature[..32].try_into().unwrap();
+    let s_bytes: [u8; 32] = signature[32..].try_into().unwrap();
+
+    let r: Scalar = Option::from(Scalar::from_repr(*FieldBytes::from_slice(&r_bytes)))
+        .ok_or_else(|| "Invalid r: not a canonical scalar".to_string())?;
+    let s: Scalar = Option::from(Scalar::from_repr(*FieldBytes::from_slice(&s_bytes)))
+        .ok_or_else(|| "Invalid s: not a canonical scalar".to_string())?;
+    if bool::from(r.is_zero()) || bool::from(s.is_zero()) {
+        return Err("r and s must be nonzero".into());
+    }
+
+    let x_bytes = if recovery_id & 0b10 != 0 {
+        add_curve_order(&r_bytes)
+            .ok_or_else(|| "r + n does not fit in a field element".to_string())?
+    } else {
+        r_bytes
+    };
+    let y_is_odd = recovery_id & 1 != 0;
+
+    let mut compressed = [0u8; 33];
+    compressed[0] = if y_is_odd { 0x03 } else { 0x02 };
+    compressed[1..].copy_from_slice(&x_bytes);
+
+    let encoded = EncodedPoint::from_bytes(compressed)
+        .map_err(|e| format!("Invalid candidate point encoding: {e}"))?;
+    let r_affine: AffinePoint = Option::from(AffinePoint::from_encoded_point(&encoded))
+        .ok_or_else(|| "Recovered point is not on the curve".to_string())?;
+
+    let e = <Scalar as Reduce<U256>>::reduce_bytes(&Sha256::digest(data));
+
+    let r_inv: Scalar = Option::from(r.invert())
+        .ok_or_else(|| "r has no inverse mod the curve order".to_string())?;
+
+    let q = (ProjectivePoint::from(r_affine) * s - ProjectivePoint::GENERATOR * e) * r_inv;
+    if bool::from(q.is_identity()) {
+        return Err("Recovered point is the identity".into());
+    }
+
+    let encoded_q = q.to_affine().to_encoded_point(false);
+    Ok(encoded_q.as_bytes()[1..].to_vec())
+}
+
+/// Recover every valid candidate public key for recovery ids `0..=3`, since
+/// the device's `signed_read` doesn't transmit which one applies.
+pub fn recover_candidates(data: &[u8], signature: &[u8]) -> Vec<Vec<u8>> {
+    (0u8..=3)
+        .filter_map(|id| recover_public_key(data, signature, id).ok())
+        .collect()
+}
+
+/// Recover candidates and return the one matching `expected_pub_key`, or an
+/// error if none do.
+pub fn recover_and_match(
+    data: &[u8],
+    signature: &[u8],
+    expected_pub_key: &[u8],
+) -> Result<Vec<u8>, String> {
+    recover_candidates(data, signature)
+        .into_iter()
+        .find(|candidate| candidate.as_slice() == expected_pub_key)
+        .ok_or_else(|| "No recovery id produced the expected public key".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +538,191 @@ mod tests {
         assert!(verify_certificate(&[0; 64], &[0; 32], &[0; 64], 1, 1, 1).is_err());
         assert!(verify_certificate(&[0; 64], &[0; 64], &[0; 32], 1, 1, 1).is_err());
     }
+
+    // -- Prehashed / DER verification tests --
+
+    #[test]
+    fn verify_signature_prehashed_valid() {
+        let (pub_key, signing_key) = test_keypair();
+        let message = b"hello quantum world";
+        let digest: [u8; 32] = Sha256::digest(message).into();
+        let sig: Signature = signing_key.sign(message);
+        let sig_bytes = sig.to_bytes();
+
+        let result = verify_signature_prehashed(&pub_key, &digest, &sig_bytes).unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn verify_signature_prehashed_wrong_digest() {
+        let (pub_key, signing_key) = test_keypair();
+        let sig: Signature = signing_key.sign(b"correct message");
+        let sig_bytes = sig.to_bytes();
+        let wrong_digest: [u8; 32] = Sha256::digest(b"wrong message").into();
+
+        let result = verify_signature_prehashed(&pub_key, &wrong_digest, &sig_bytes).unwrap();
+        assert!(!result);
+    }
+
+    #[test]
+    fn verify_signature_prehashed_bad_key_length() {
+        let result = verify_signature_prehashed(&[0u8; 32], &[0u8; 32], &[0u8; 64]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_signature_der_valid() {
+        let (pub_key, signing_key) = test_keypair();
+        let message = b"hello quantum world";
+        let sig: Signature = signing_key.sign(message);
+        let der_sig = sig.to_der();
+
+        let result = verify_signature_der(&pub_key, message, der_sig.as_bytes()).unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn verify_signature_der_wrong_message() {
+        let (pub_key, signing_key) = test_keypair();
+        let sig: Signature = signing_key.sign(b"correct message");
+        let der_sig = sig.to_der();
+
+        let result =
+            verify_signature_der(&pub_key, b"wrong message", der_sig.as_bytes()).unwrap();
+        assert!(!result);
+    }
+
+    #[test]
+    fn verify_signature_der_malformed() {
+        let (pub_key, _) = test_keypair();
+        assert!(verify_signature_der(&pub_key, b"msg", &[0xde, 0xad, 0xbe, 0xef]).is_err());
+    }
+
+    // -- Public-key recovery tests --
+
+    #[test]
+    fn recover_candidates_includes_the_signing_key() {
+        let (pub_key, signing_key) = test_keypair();
+        let message = b"recoverable message";
+        let sig: Signature = signing_key.sign(message);
+        let sig_bytes = sig.to_bytes();
+
+        let candidates = recover_candidates(message, &sig_bytes);
+        assert!(
+            candidates.iter().any(|c| c == &pub_key),
+            "expected {pub_key:02x?} among candidates {candidates:02x?}"
+        );
+    }
+
+    #[test]
+    fn recover_and_match_finds_expected_key() {
+        let (pub_key, signing_key) = test_keypair();
+        let message = b"recoverable message";
+        let sig: Signature = signing_key.sign(message);
+        let sig_bytes = sig.to_bytes();
+
+        let recovered = recover_and_match(message, &sig_bytes, &pub_key).unwrap();
+        assert_eq!(recovered, pub_key);
+    }
+
+    #[test]
+    fn recover_and_match_rejects_wrong_key() {
+        let (_pub_key, signing_key) = test_keypair();
+        let message = b"recoverable message";
+        let sig: Signature = signing_key.sign(message);
+        let sig_bytes = sig.to_bytes();
+
+        assert!(recover_and_match(message, &sig_bytes, &[0x99; 64]).is_err());
+    }
+
+    #[test]
+    fn recover_public_key_bad_sig_length() {
+        assert!(recover_public_key(b"msg", &[0u8; 32], 0).is_err());
+    }
+
+    #[test]
+    fn recover_public_key_bad_recovery_id() {
+        let (_pub_key, signing_key) = test_keypair();
+        let sig: Signature = signing_key.sign(b"msg");
+        assert!(recover_public_key(b"msg", &sig.to_bytes(), 4).is_err());
+    }
+
+    #[test]
+    fn recover_public_key_rejects_zero_r_or_s() {
+        let mut sig = [0u8; 64];
+        sig[31] = 1; // nonzero r, zero s
+        assert!(recover_public_key(b"msg", &sig, 0).is_err());
+    }
+
+    // -- Pluggable backend tests --
+
+    /// Test double that ignores its inputs and always returns `decision`.
+    struct StubBackend {
+        decision: Result<bool, String>,
+    }
+
+    impl EcdsaP256Verifier for StubBackend {
+        fn verify(&self, _pub_key: &[u8; 64], _message: &[u8], _sig: &[u8; 64]) -> Result<bool, String> {
+            self.decision.clone()
+        }
+    }
+
+    #[test]
+    fn verify_signature_with_backend_uses_supplied_backend() {
+        let (pub_key, _signing_key) = test_keypair();
+        let stub = StubBackend { decision: Ok(true) };
+
+        let result = verify_signature_with_backend(&stub, &pub_key, b"msg", &[0u8; 64]).unwrap();
+        assert!(result);
+    }
+
+    #[test]
+    fn verify_signature_with_backend_propagates_backend_error() {
+        let (pub_key, _signing_key) = test_keypair();
+        let stub = StubBackend {
+            decision: Err("backend unavailable".into()),
+        };
+
+        let err = verify_signature_with_backend(&stub, &pub_key, b"msg", &[0u8; 64]).unwrap_err();
+        assert_eq!(err, "backend unavailable");
+    }
+
+    #[test]
+    fn verify_signature_default_matches_rust_crypto_backend() {
+        let (pub_key, signing_key) = test_keypair();
+        let message = b"hello quantum world";
+        let sig: Signature = signing_key.sign(message);
+        let sig_bytes = sig.to_bytes();
+
+        let via_free_fn = verify_signature(&pub_key, message, &sig_bytes).unwrap();
+        let via_backend =
+            verify_signature_with_backend(&RustCryptoBackend, &pub_key, message, &sig_bytes)
+                .unwrap();
+        assert_eq!(via_free_fn, via_backend);
+        assert!(via_free_fn);
+    }
+
+    #[test]
+    fn verify_certificate_default_matches_rust_crypto_backend() {
+        let (ca_pub, ca_signing) = test_keypair();
+        let device_pub = vec![0x42; 64];
+        let cert_data = build_certificate_data(1, 1, 217, &device_pub);
+        let sig: Signature = ca_signing.sign(&cert_data);
+        let certificate = sig.to_bytes().to_vec();
+
+        let via_free_fn =
+            verify_certificate(&ca_pub, &device_pub, &certificate, 1, 1, 217).unwrap();
+        let via_backend = verify_certificate_with_backend(
+            &RustCryptoBackend,
+            &ca_pub,
+            &device_pub,
+            &certificate,
+            1,
+            1,
+            217,
+        )
+        .unwrap();
+        assert_eq!(via_free_fn, via_backend);
+        assert!(via_free_fn);
+    }
 }
@@ -3,8 +3,10 @@
 use std::io;
 use std::time::Duration;
 
+use crate::health::{ContinuousTests, HealthConfig};
 use crate::protocol::*;
 use crate::serial::{find_devices, SerialTransport};
+use crate::trust::{self, Attestation, TrustStore};
 use crate::types::*;
 use crate::QCicadaError;
 
@@ -21,6 +23,8 @@ use crate::QCicadaError;
 /// ```
 pub struct QCicada {
     transport: SerialTransport,
+    health: Option<ContinuousTests>,
+    attested_key: Option<Vec<u8>>,
 }
 
 impl QCicada {
@@ -43,7 +47,54 @@ impl QCicada {
         };
 
         let transport = SerialTransport::open(&port_name, timeout)?;
-        Ok(Self { transport })
+        Ok(Self {
+            transport,
+            health: None,
+            attested_key: None,
+        })
+    }
+
+    /// Enable host-side SP 800-90B continuous health tests (Repetition Count
+    /// and Adaptive Proportion) on the output of [`random`](Self::random),
+    /// [`signed_read`](Self::signed_read), and
+    /// [`read_continuous`](Self::read_continuous).
+    ///
+    /// Once enabled, those methods fail closed with
+    /// [`QCicadaError::HealthTestFailed`] the moment either test alarms.
+    pub fn set_health_config(&mut self, config: HealthConfig) {
+        self.health = Some(ContinuousTests::new(&config));
+    }
+
+    /// Cumulative (Repetition Count, Adaptive Proportion) health-test failure
+    /// counts observed host-side, for cross-checking against
+    /// [`DeviceStatistics`]. `None` if no [`HealthConfig`] has been set.
+    pub fn health_failures(&self) -> Option<(u64, u64)> {
+        self.health
+            .as_ref()
+            .map(|h| (h.repetition_count_failures(), h.adaptive_proportion_failures()))
+    }
+
+    /// Compare the host-side health-test counters against the device's own
+    /// `repetition_count_failures`/`adaptive_proportion_failures` (from
+    /// [`get_statistics`](Self::get_statistics)), returning `true` if both
+    /// agree. Always `true` if no [`HealthConfig`] has been set.
+    pub fn health_matches_statistics(&self, stats: &DeviceStatistics) -> bool {
+        match self.health_failures() {
+            Some((rct, apt)) => {
+                rct == stats.repetition_count_failures as u64
+                    && apt == stats.adaptive_proportion_failures as u64
+            }
+            None => true,
+        }
+    }
+
+    fn check_health(&mut self, data: &[u8]) -> Result<(), QCicadaError> {
+        if let Some(health) = &mut self.health {
+            health
+                .check(data)
+                .map_err(|offset| QCicadaError::HealthTestFailed { offset })?;
+        }
+        Ok(())
     }
 
     /// Read device identification (version, serial, hardware).
@@ -97,7 +148,7 @@ impl QCicada {
         // Read the random data
         let timeout_ms = 500 + (n as u64) / 10;
         self.transport
-            .set_timeout(Duration::from_millis(timeout_ms))?;
+            .set_timeout(Some(Duration::from_millis(timeout_ms)))?;
         let data = self.transport.read(n as usize)?;
         if data.len() != n as usize {
             return Err(QCicadaError::Protocol(format!(
@@ -106,6 +157,7 @@ impl QCicada {
                 data.len()
             )));
         }
+        self.check_health(&data)?;
         Ok(data)
     }
 
@@ -127,7 +179,7 @@ impl QCicada {
         let total = n as usize + SIGNATURE_LEN;
         let timeout_ms = 500 + (n as u64) / 10;
         self.transport
-            .set_timeout(Duration::from_millis(timeout_ms))?;
+            .set_timeout(Some(Duration::from_millis(timeout_ms)))?;
         let buf = self.transport.read(total)?;
         if buf.len() != total {
             return Err(QCicadaError::Protocol(format!(
@@ -136,6 +188,7 @@ impl QCicada {
                 buf.len()
             )));
         }
+        self.check_health(&buf[..n as usize])?;
         Ok(SignedRead {
             data: buf[..n as usize].to_vec(),
             signature: buf[n as usize..].to_vec(),
@@ -162,7 +215,7 @@ impl QCicada {
         }
         let timeout_ms = 500 + (n as u64) / 10;
         self.transport
-            .set_timeout(Duration::from_millis(timeout_ms))?;
+            .set_timeout(Some(Duration::from_millis(timeout_ms)))?;
         let data = self.transport.read(n)?;
         if data.len() != n {
             return Err(QCicadaError::Protocol(format!(
@@ -171,9 +224,24 @@ impl QCicada {
                 data.len()
             )));
         }
+        self.check_health(&data)?;
         Ok(data)
     }
 
+    /// Start continuous mode and hand it off to a dedicated reader thread.
+    ///
+    /// Unlike [`read_continuous`](Self::read_continuous), the returned
+    /// [`RandomStream`] reads on its own thread over a cloned port handle, so
+    /// consuming it doesn't block on FTDI read latency. Dropping or calling
+    /// [`RandomStream::stop`] halts continuous mode and restores this
+    /// connection to a clean state — no other method should be called on
+    /// `self` while a stream is active.
+    pub fn stream(&mut self) -> Result<crate::stream::RandomStream, QCicadaError> {
+        self.start_continuous()?;
+        let cloned = self.transport.try_clone_port()?;
+        Ok(crate::stream::RandomStream::spawn(cloned))
+    }
+
     /// Retrieve the device's ECDSA P-256 public key (64 bytes: x || y).
     ///
     /// Requires QCicada firmware with certificate support.
@@ -283,6 +351,61 @@ impl QCicada {
         Ok(result)
     }
 
+    /// Prove the device is CA-attested, with no caller-supplied CA key.
+    ///
+    /// Fetches the device's public key, certificate, and info, then verifies
+    /// the certificate chain against every root in `trust_store`, returning
+    /// an [`Attestation`] for the one that validates. Unlike
+    /// [`get_verified_pub_key`](Self::get_verified_pub_key), the caller
+    /// doesn't need to already know the right CA key.
+    pub fn attest(&mut self, trust_store: &TrustStore) -> Result<Attestation, QCicadaError> {
+        let info = self.get_info()?;
+        let dev_pub_key = self.get_dev_pub_key()?;
+        let certificate = self.get_dev_certificate()?;
+
+        let (hw_major, hw_minor) = crate::protocol::parse_hw_version(&info.hw_info)
+            .ok_or_else(|| {
+                QCicadaError::Protocol(format!(
+                    "Cannot parse hardware version from '{}'",
+                    info.hw_info
+                ))
+            })?;
+        let serial_int = crate::protocol::parse_serial_int(&info.serial).ok_or_else(|| {
+            QCicadaError::Protocol(format!(
+                "Cannot parse serial number from '{}'",
+                info.serial
+            ))
+        })?;
+
+        trust::verify_trust_chain(
+            trust_store,
+            &dev_pub_key,
+            &certificate,
+            hw_major,
+            hw_minor,
+            serial_int,
+        )
+    }
+
+    /// Perform a signed read, attesting the device against `trust_store` on
+    /// the first call and caching the verified device key for every
+    /// subsequent call on this connection.
+    ///
+    /// A single call proves both "this is a real QCicada" (the attestation)
+    /// and "this data came from it" (the per-read signature check).
+    pub fn signed_read_attested(
+        &mut self,
+        n: u16,
+        trust_store: &TrustStore,
+    ) -> Result<SignedRead, QCicadaError> {
+        if self.attested_key.is_none() {
+            let attestation = self.attest(trust_store)?;
+            self.attested_key = Some(attestation.pub_key);
+        }
+        let device_pub_key = self.attested_key.clone().expect("just populated above");
+        self.signed_read_verified(n, &device_pub_key)
+    }
+
     /// Reboot the device.
     ///
     /// Sends the QCicada-specific reboot command. The device will disconnect
@@ -293,7 +416,7 @@ impl QCicada {
         self.transport.flush()?;
         self.transport.write(&frame)?;
         // Read optional response — device may disconnect immediately
-        self.transport.set_timeout(Duration::from_millis(500))?;
+        self.transport.set_timeout(Some(Duration::from_millis(500)))?;
         let _ = self.transport.read(1);
         Ok(())
     }
@@ -318,6 +441,51 @@ impl QCicada {
         Ok(())
     }
 
+    /// Flash a new firmware `image` onto the device.
+    ///
+    /// Splits `image` into checksummed blocks via
+    /// [`protocol::split_firmware_image`], then sends `FW_BEGIN`, one
+    /// `FW_DATA` frame per block (validating the device's per-block ACK/NACK
+    /// before sending the next), and `FW_END`.
+    ///
+    /// The device disconnects and reboots on success — re-open the connection
+    /// afterwards, as with [`reboot`](Self::reboot).
+    pub fn update_firmware(&mut self, image: &[u8]) -> Result<(), QCicadaError> {
+        self.transport.flush()?;
+        self.transport.write(&build_fw_begin(image.len() as u32))?;
+        self.transport.set_timeout(Some(Duration::from_secs(3)))?;
+        parse_fw_block_response(&self.read_fw_block_response()?)?;
+
+        for block in split_firmware_image(image) {
+            self.transport.write(&build_fw_data(&block))?;
+            parse_fw_block_response(&self.read_fw_block_response()?)?;
+        }
+
+        self.transport.write(&build_fw_end())?;
+        parse_fw_block_response(&self.read_fw_block_response()?)?;
+        Ok(())
+    }
+
+    /// Read one FW_BEGIN/FW_DATA/FW_END response: a 1-byte response code,
+    /// followed by [`PAYLOAD_ACK`] status bytes on [`RESP_ACK`] or a single
+    /// reason byte on [`RESP_NACK`] — the same 1-byte-code-then-payload
+    /// pattern [`command`](Self::command) uses for every other response.
+    fn read_fw_block_response(&mut self) -> Result<Vec<u8>, QCicadaError> {
+        let mut resp = self.transport.read(1)?;
+        let Some(&code) = resp.first() else {
+            return Ok(resp);
+        };
+        let extra = match code {
+            RESP_ACK => PAYLOAD_ACK,
+            RESP_NACK => 1,
+            _ => 0,
+        };
+        if extra > 0 {
+            resp.extend(self.transport.read(extra)?);
+        }
+        Ok(resp)
+    }
+
     /// Fill a buffer with random bytes, chunking as needed for the protocol limit.
     pub fn fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), QCicadaError> {
         let mut offset = 0;
@@ -361,7 +529,7 @@ impl QCicada {
         }
 
         // Read 1-byte response code
-        self.transport.set_timeout(Duration::from_secs(3))?;
+        self.transport.set_timeout(Some(Duration::from_secs(3)))?;
         let resp = self.transport.read(1)?;
         if resp.is_empty() {
             return Ok(None);
@@ -374,7 +542,7 @@ impl QCicada {
             }
             let timeout_ms = (size as u64).max(500);
             self.transport
-                .set_timeout(Duration::from_millis(timeout_ms))?;
+                .set_timeout(Some(Duration::from_millis(timeout_ms)))?;
             let resp_payload = self.transport.read(size)?;
             if resp_payload.len() != size {
                 return Ok(None);
@@ -394,7 +562,7 @@ impl QCicada {
     fn handle_stop(&mut self) -> Result<Option<Vec<u8>>, QCicadaError> {
         let drain_size = MAX_BLOCK_SIZE * 2 + PAYLOAD_ACK + 1;
 
-        self.transport.set_timeout(Duration::from_millis(500))?;
+        self.transport.set_timeout(Some(Duration::from_millis(500)))?;
 
         for _ in 0..2 {
             let resp = self.transport.read(drain_size)?;
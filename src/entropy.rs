@@ -0,0 +1,106 @@
+//! Min-entropy estimation for raw output streams.
+//!
+//! Lets operators verify conditioning assumptions before trusting
+//! [`PostProcess::Sha256`](crate::types::PostProcess::Sha256) mode by
+//! estimating the min-entropy of the device's `RawSamples`/`RawNoise` output.
+//! Implements the SP 800-90B "Most Common Value" estimator (6.3.1).
+
+/// Result of the Most Common Value estimator: the observed mode count, the
+/// upper confidence bound on its probability, and the resulting min-entropy
+/// estimate in bits per sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MostCommonValueEstimate {
+    /// Total number of samples the estimate was computed over.
+    pub sample_count: usize,
+    /// Count of the single most frequent value among the samples.
+    pub mode_count: usize,
+    /// Upper confidence bound `p_u` on the mode's true probability.
+    pub p_u: f64,
+    /// Estimated min-entropy, `-log2(p_u)`, in bits per sample.
+    pub bits_per_sample: f64,
+}
+
+/// Estimate min-entropy over `samples` using the SP 800-90B "Most Common
+/// Value" estimator.
+///
+/// `samples` should already be reduced to the target symbol alphabet — e.g.
+/// mask each raw byte down to `n_lsbits` (from
+/// [`DeviceConfig`](crate::types::DeviceConfig)) before calling this, so the
+/// mode is computed over the actual output alphabet rather than full bytes.
+///
+/// Returns `None` if `samples` has fewer than 2 elements (the confidence
+/// bound divides by `n - 1`).
+pub fn most_common_value_estimate(samples: &[u8]) -> Option<MostCommonValueEstimate> {
+    let n = samples.len();
+    if n < 2 {
+        return None;
+    }
+
+    let mut counts = [0usize; 256];
+    for &s in samples {
+        counts[s as usize] += 1;
+    }
+    let mode_count = counts.into_iter().max().unwrap_or(0);
+
+    let p_hat = mode_count as f64 / n as f64;
+    let p_u = (p_hat + 2.576 * (p_hat * (1.0 - p_hat) / (n as f64 - 1.0)).sqrt()).min(1.0);
+    let bits_per_sample = -p_u.log2();
+
+    Some(MostCommonValueEstimate {
+        sample_count: n,
+        mode_count,
+        p_u,
+        bits_per_sample,
+    })
+}
+
+/// Mask each byte in `samples` down to its `n_lsbits` least-significant bits,
+/// matching the device's configured output alphabet (`DeviceConfig::n_lsbits`).
+/// An `n_lsbits` of 0, or of 8 and above, returns the samples unchanged.
+pub fn to_symbol_alphabet(samples: &[u8], n_lsbits: u8) -> Vec<u8> {
+    if n_lsbits == 0 || n_lsbits >= 8 {
+        return samples.to_vec();
+    }
+    let mask = (1u8 << n_lsbits) - 1;
+    samples.iter().map(|&b| b & mask).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_samples_estimate_near_full_entropy() {
+        let samples: Vec<u8> = (0..=255).cycle().take(25600).collect();
+        let estimate = most_common_value_estimate(&samples).unwrap();
+        assert_eq!(estimate.sample_count, 25600);
+        assert!(estimate.bits_per_sample > 7.0, "{estimate:?}");
+    }
+
+    #[test]
+    fn constant_samples_estimate_zero_entropy() {
+        let samples = vec![0x42u8; 1000];
+        let estimate = most_common_value_estimate(&samples).unwrap();
+        assert_eq!(estimate.mode_count, 1000);
+        assert!(estimate.bits_per_sample < 0.1, "{estimate:?}");
+    }
+
+    #[test]
+    fn too_few_samples_returns_none() {
+        assert!(most_common_value_estimate(&[]).is_none());
+        assert!(most_common_value_estimate(&[1]).is_none());
+    }
+
+    #[test]
+    fn to_symbol_alphabet_masks_bits() {
+        let samples = [0xFF, 0x0F, 0b1010];
+        assert_eq!(to_symbol_alphabet(&samples, 4), vec![0x0F, 0x0F, 0b1010]);
+        assert_eq!(to_symbol_alphabet(&samples, 1), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn to_symbol_alphabet_passthrough_for_full_byte() {
+        let samples = [0x12, 0x34];
+        assert_eq!(to_symbol_alphabet(&samples, 8), samples.to_vec());
+    }
+}
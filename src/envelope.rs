@@ -0,0 +1,198 @@
+//! Self-contained, independently verifiable signed-randomness envelopes.
+//!
+//! Packages one [`SignedRead`] into a portable record — the random bytes,
+//! signature, device identity, public key, and optionally the CA certificate —
+//! that can be persisted to disk or shipped over the network and verified
+//! later with no device present. The `Serialize`/`Deserialize` derives are
+//! enabled via the `serde` feature.
+
+use crate::crypto::{verify_certificate, verify_signature};
+use crate::device::QCicada;
+use crate::protocol::{parse_hw_version, parse_serial_int};
+use crate::QCicadaError;
+
+/// A portable, independently verifiable signed-randomness record.
+///
+/// Produced by [`QCicada::signed_read_envelope`]; checked offline by
+/// [`verify_envelope`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedEnvelope {
+    /// The random bytes.
+    pub data: Vec<u8>,
+    /// 64-byte ECDSA signature over `data`.
+    pub signature: Vec<u8>,
+    /// Device serial number, e.g. `"QC0000000217"`.
+    pub serial: String,
+    /// Device hardware version string, e.g. `"CICADA-QRNG-1.1"`.
+    pub hw_version: String,
+    /// 64-byte (x || y) device public key.
+    pub device_pub_key: Vec<u8>,
+    /// 64-byte CA certificate (r || s) over the device's identity, if fetched.
+    pub ca_certificate: Option<Vec<u8>>,
+}
+
+impl QCicada {
+    /// Produce a [`SignedEnvelope`]: `n` signed random bytes plus everything
+    /// an offline verifier needs (device identity, public key, and
+    /// certificate) to confirm it later via [`verify_envelope`].
+    pub fn signed_read_envelope(&mut self, n: u16) -> Result<SignedEnvelope, QCicadaError> {
+        let signed = self.signed_read(n)?;
+        let info = self.get_info()?;
+        let device_pub_key = self.get_dev_pub_key()?;
+        let ca_certificate = self.get_dev_certificate().ok();
+
+        Ok(SignedEnvelope {
+            data: signed.data,
+            signature: signed.signature,
+            serial: info.serial,
+            hw_version: info.hw_info,
+            device_pub_key,
+            ca_certificate,
+        })
+    }
+}
+
+/// Verify a [`SignedEnvelope`] with no device present: re-runs
+/// [`verify_certificate`] (the device key is CA-signed) and
+/// [`verify_signature`] (the data matches the signature).
+///
+/// Fails closed: an envelope with no `ca_certificate` can't bind
+/// `device_pub_key` to any identity, so it's rejected rather than silently
+/// falling back to a signature-only check against an untrusted key. Use
+/// [`verify_envelope_signature_only`] if that's genuinely what's wanted.
+pub fn verify_envelope(env: &SignedEnvelope, ca_pub_key: &[u8]) -> Result<(), QCicadaError> {
+    let certificate = env.ca_certificate.as_ref().ok_or_else(|| {
+        QCicadaError::Signature(
+            "Envelope has no ca_certificate; use verify_envelope_signature_only if identity \
+             binding isn't required"
+                .into(),
+        )
+    })?;
+
+    let (hw_major, hw_minor) = parse_hw_version(&env.hw_version).ok_or_else(|| {
+        QCicadaError::Signature(format!(
+            "Cannot parse hardware version from '{}'",
+            env.hw_version
+        ))
+    })?;
+    let serial_int = parse_serial_int(&env.serial).ok_or_else(|| {
+        QCicadaError::Signature(format!("Cannot parse serial number from '{}'", env.serial))
+    })?;
+
+    let valid = verify_certificate(
+        ca_pub_key,
+        &env.device_pub_key,
+        certificate,
+        hw_major,
+        hw_minor,
+        serial_int,
+    )
+    .map_err(QCicadaError::Signature)?;
+    if !valid {
+        return Err(QCicadaError::Signature(
+            "Device certificate verification failed".into(),
+        ));
+    }
+
+    verify_envelope_signature_only(env)
+}
+
+/// Verify only that `env.signature` matches `env.data` under `env.device_pub_key`.
+///
+/// Unlike [`verify_envelope`], this does **not** establish that
+/// `device_pub_key` belongs to a genuine, CA-attested QCicada — an envelope's
+/// `device_pub_key` is caller-supplied data, not a trusted identity, so this
+/// function alone proves only internal self-consistency. Only use it when
+/// the device's identity is already known/trusted through some other channel.
+pub fn verify_envelope_signature_only(env: &SignedEnvelope) -> Result<(), QCicadaError> {
+    let valid = verify_signature(&env.device_pub_key, &env.data, &env.signature)
+        .map_err(QCicadaError::Signature)?;
+    if !valid {
+        return Err(QCicadaError::Signature(
+            "Envelope signature does not match data".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+    use p256::SecretKey;
+
+    use crate::protocol::build_certificate_data;
+
+    fn keypair(seed: u8) -> ([u8; 64], SigningKey) {
+        let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+        let signing = SigningKey::from(secret);
+        let point = signing.verifying_key().to_encoded_point(false);
+        let raw: [u8; 64] = point.as_bytes()[1..].try_into().unwrap();
+        (raw, signing)
+    }
+
+    fn envelope_with_certificate(serial: &str, hw_version: &str) -> (SignedEnvelope, [u8; 64]) {
+        let (ca_pub, ca_signing) = keypair(0x22);
+        let (device_pub, device_signing) = keypair(0x33);
+
+        let data = vec![0xAB; 16];
+        let sig: Signature = device_signing.sign(&data);
+
+        let cert_data = build_certificate_data(1, 1, 217, &device_pub);
+        let cert_sig: Signature = ca_signing.sign(&cert_data);
+
+        let envelope = SignedEnvelope {
+            data,
+            signature: sig.to_bytes().to_vec(),
+            serial: serial.to_string(),
+            hw_version: hw_version.to_string(),
+            device_pub_key: device_pub.to_vec(),
+            ca_certificate: Some(cert_sig.to_bytes().to_vec()),
+        };
+        (envelope, ca_pub)
+    }
+
+    #[test]
+    fn verify_envelope_with_valid_certificate() {
+        let (envelope, ca_pub) = envelope_with_certificate("QC0000000217", "CICADA-QRNG-1.1");
+        verify_envelope(&envelope, &ca_pub).unwrap();
+    }
+
+    #[test]
+    fn verify_envelope_rejects_unparseable_hw_version() {
+        let (envelope, ca_pub) = envelope_with_certificate("QC0000000217", "CICADA-QRNG");
+        let err = verify_envelope(&envelope, &ca_pub).unwrap_err();
+        assert!(err.to_string().contains("hardware version"));
+    }
+
+    #[test]
+    fn verify_envelope_rejects_unparseable_serial() {
+        let (envelope, ca_pub) = envelope_with_certificate("NOTASERIAL", "CICADA-QRNG-1.1");
+        let err = verify_envelope(&envelope, &ca_pub).unwrap_err();
+        assert!(err.to_string().contains("serial number"));
+    }
+
+    #[test]
+    fn verify_envelope_without_certificate_fails_closed() {
+        let (mut envelope, _ca_pub) = envelope_with_certificate("QC0000000217", "CICADA-QRNG-1.1");
+        envelope.ca_certificate = None;
+        let err = verify_envelope(&envelope, &[0u8; 64]).unwrap_err();
+        assert!(err.to_string().contains("ca_certificate"));
+    }
+
+    #[test]
+    fn verify_envelope_signature_only_ignores_identity() {
+        let (mut envelope, _ca_pub) = envelope_with_certificate("QC0000000217", "CICADA-QRNG-1.1");
+        envelope.ca_certificate = None;
+        verify_envelope_signature_only(&envelope).unwrap();
+    }
+
+    #[test]
+    fn verify_envelope_signature_only_rejects_tampered_data() {
+        let (mut envelope, _ca_pub) = envelope_with_certificate("QC0000000217", "CICADA-QRNG-1.1");
+        envelope.data[0] ^= 0xFF;
+        assert!(verify_envelope_signature_only(&envelope).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! NIST SP 800-90B continuous health tests over the raw entropy stream.
+//!
+//! [`DeviceStatus`](crate::types::DeviceStatus) exposes `repetition_count`/
+//! `adaptive_proportion` flags computed on-device, but when pulling
+//! `RawNoise`/`RawSamples` output ([`PostProcess`](crate::types::PostProcess))
+//! there was previously no way to independently validate the stream host-side.
+//! [`ContinuousTests`] fills that gap: feed it samples incrementally (either
+//! wired into [`QCicada::random`]/[`signed_read`](crate::device::QCicada::signed_read)/
+//! [`read_continuous`](crate::device::QCicada::read_continuous) via
+//! [`QCicada::set_health_config`](crate::device::QCicada::set_health_config),
+//! or run standalone over a buffer you already pulled) and it flags failures,
+//! failing closed when the source degrades. Implements the two SP 800-90B
+//! continuous tests byte-by-byte; see [`RepetitionCountTest`] and
+//! [`AdaptiveProportionTest`].
+
+/// Adaptive Proportion Test window for binary-valued samples (1 bit/sample),
+/// per SP 800-90B.
+pub const APT_WINDOW_BINARY: usize = 512;
+/// Adaptive Proportion Test window for all other (non-binary) sample widths.
+pub const APT_WINDOW_NON_BINARY: usize = 1024;
+
+/// Configuration for the health-test layer.
+///
+/// Pass to [`QCicada::set_health_config`](crate::device::QCicada::set_health_config)
+/// after opening to enable continuous validation of the device's raw byte
+/// stream.
+#[derive(Debug, Clone, Copy)]
+pub struct HealthConfig {
+    /// False-positive target `alpha`, e.g. `2^-20`.
+    pub alpha: f64,
+    /// Configured per-sample min-entropy estimate `H`, in bits.
+    pub min_entropy_bits: f64,
+    /// Adaptive Proportion Test window size `W`, in samples.
+    pub apt_window: usize,
+}
+
+impl HealthConfig {
+    /// NIST SP 800-90B's suggested default: `alpha = 2^-20`, `W = 512` (the
+    /// binary-valued-sample window; use [`HealthConfig::for_symbol_bits`] when
+    /// samples are wider, e.g. the device's `n_lsbits`-sized symbols).
+    pub fn new(min_entropy_bits: f64) -> Self {
+        Self {
+            alpha: 2f64.powi(-20),
+            min_entropy_bits,
+            apt_window: APT_WINDOW_BINARY,
+        }
+    }
+
+    /// Like [`new`](Self::new), but picks the Adaptive Proportion Test window
+    /// per SP 800-90B based on the sample's symbol width: `512` for
+    /// single-bit (binary) samples, `1024` otherwise.
+    pub fn for_symbol_bits(min_entropy_bits: f64, symbol_bits: u8) -> Self {
+        let apt_window = if symbol_bits <= 1 {
+            APT_WINDOW_BINARY
+        } else {
+            APT_WINDOW_NON_BINARY
+        };
+        Self {
+            apt_window,
+            ..Self::new(min_entropy_bits)
+        }
+    }
+
+    fn rct_cutoff(&self) -> u64 {
+        1 + (-self.alpha.log2() / self.min_entropy_bits).ceil() as u64
+    }
+
+    /// Adaptive Proportion Test cutoff `C`: the smallest integer such that the
+    /// binomial tail `P(X >= C)` with per-sample match probability `2^-H`
+    /// over `W - 1` trials falls below `alpha`.
+    fn apt_cutoff(&self) -> u64 {
+        let p = 2f64.powf(-self.min_entropy_bits);
+        let trials = (self.apt_window - 1) as u64;
+        let pmf = binomial_pmf_table(trials, p);
+
+        // sf(c) = P(X >= c) is non-increasing in c: walk c from trials down
+        // to 1, accumulating the tail sum (so `tail` is always sf(c) for the
+        // current c), and keep the smallest c seen while it's still below
+        // alpha. Once it's no longer below alpha, smaller c only grows the
+        // tail further, so stop.
+        let mut tail = 0.0;
+        let mut best = trials;
+        for c in (1..=trials).rev() {
+            tail += pmf[c as usize];
+            if tail < self.alpha {
+                best = c;
+            } else {
+                break;
+            }
+        }
+        best
+    }
+}
+
+/// `P(X = k)` for `X ~ Binomial(n, p)`, `k = 0..=n`, via the standard pmf
+/// recurrence `pmf(k+1) = pmf(k) * (n-k)/(k+1) * p/(1-p)` — O(n) total,
+/// rather than recomputing each term's factorials from scratch.
+fn binomial_pmf_table(n: u64, p: f64) -> Vec<f64> {
+    let mut pmf = Vec::with_capacity(n as usize + 1);
+    pmf.push((1.0 - p).powf(n as f64));
+    for k in 0..n {
+        let next = pmf[k as usize] * (n - k) as f64 / (k + 1) as f64 * p / (1.0 - p);
+        pmf.push(next);
+    }
+    pmf
+}
+
+/// Repetition Count Test (SP 800-90B 4.4.1).
+///
+/// Tracks the current sample value and a run length; on each new sample,
+/// increments the run if it equals the previous one, else resets to 1, and
+/// alarms when the run reaches `C = 1 + ceil(-log2(alpha) / H)`.
+pub struct RepetitionCountTest {
+    cutoff: u64,
+    current: Option<u8>,
+    run: u64,
+    failures: u64,
+}
+
+impl RepetitionCountTest {
+    pub fn new(config: &HealthConfig) -> Self {
+        Self {
+            cutoff: config.rct_cutoff(),
+            current: None,
+            run: 0,
+            failures: 0,
+        }
+    }
+
+    /// Feed one sample. Returns `true` if this sample triggered an alarm.
+    pub fn update(&mut self, sample: u8) -> bool {
+        if self.current == Some(sample) {
+            self.run += 1;
+        } else {
+            self.current = Some(sample);
+            self.run = 1;
+        }
+        if self.run >= self.cutoff {
+            self.failures += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cumulative number of alarms raised since construction.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+}
+
+/// Adaptive Proportion Test (SP 800-90B 4.4.2).
+///
+/// Processes fixed windows of `config.apt_window` samples: the first sample
+/// of each window is the reference; alarms if the count of subsequent matches
+/// within the window reaches the cutoff.
+pub struct AdaptiveProportionTest {
+    cutoff: u64,
+    window: usize,
+    reference: Option<u8>,
+    matches: u64,
+    seen_in_window: usize,
+    failures: u64,
+}
+
+impl AdaptiveProportionTest {
+    pub fn new(config: &HealthConfig) -> Self {
+        Self {
+            cutoff: config.apt_cutoff(),
+            window: config.apt_window,
+            reference: None,
+            matches: 0,
+            seen_in_window: 0,
+            failures: 0,
+        }
+    }
+
+    /// Feed one sample. Returns `true` if this sample triggered an alarm.
+    pub fn update(&mut self, sample: u8) -> bool {
+        if self.reference.is_none() {
+            self.reference = Some(sample);
+            self.matches = 0;
+            self.seen_in_window = 1;
+            return false;
+        }
+
+        self.seen_in_window += 1;
+        if self.reference == Some(sample) {
+            self.matches += 1;
+        }
+
+        let alarmed = self.matches >= self.cutoff;
+        if alarmed {
+            self.failures += 1;
+        }
+        if self.seen_in_window >= self.window {
+            self.reference = None;
+        }
+        alarmed
+    }
+
+    /// Cumulative number of alarms raised since construction.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+}
+
+/// Runs both continuous tests over a single byte stream.
+pub struct ContinuousTests {
+    rct: RepetitionCountTest,
+    apt: AdaptiveProportionTest,
+}
+
+impl ContinuousTests {
+    pub fn new(config: &HealthConfig) -> Self {
+        Self {
+            rct: RepetitionCountTest::new(config),
+            apt: AdaptiveProportionTest::new(config),
+        }
+    }
+
+    /// Feed a chunk of bytes, returning `Err` with the offending byte index
+    /// on the first alarm raised by either test.
+    pub fn check(&mut self, data: &[u8]) -> Result<(), usize> {
+        for (i, &b) in data.iter().enumerate() {
+            let rct_alarm = self.rct.update(b);
+            let apt_alarm = self.apt.update(b);
+            if rct_alarm || apt_alarm {
+                return Err(i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Cumulative Repetition Count Test failures.
+    pub fn repetition_count_failures(&self) -> u64 {
+        self.rct.failures()
+    }
+
+    /// Cumulative Adaptive Proportion Test failures.
+    pub fn adaptive_proportion_failures(&self) -> u64 {
+        self.apt.failures()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rct_alarms_on_long_run() {
+        let config = HealthConfig::new(1.0); // H=1 bit/byte -> small cutoff
+        let mut rct = RepetitionCountTest::new(&config);
+        let cutoff = config.rct_cutoff();
+        let mut alarmed = false;
+        for _ in 0..cutoff {
+            alarmed |= rct.update(0xAA);
+        }
+        assert!(alarmed);
+        assert_eq!(rct.failures(), 1);
+    }
+
+    #[test]
+    fn rct_no_alarm_on_varying_samples() {
+        let config = HealthConfig::new(8.0);
+        let mut rct = RepetitionCountTest::new(&config);
+        for b in 0u8..=255 {
+            assert!(!rct.update(b));
+        }
+    }
+
+    #[test]
+    fn apt_alarms_on_dense_window() {
+        let config = HealthConfig::new(1.0);
+        let mut apt = AdaptiveProportionTest::new(&config);
+        let mut alarmed = false;
+        for _ in 0..config.apt_window {
+            alarmed |= apt.update(0x55);
+        }
+        assert!(alarmed);
+    }
+
+    #[test]
+    fn apt_window_depends_on_symbol_width() {
+        let binary = HealthConfig::for_symbol_bits(1.0, 1);
+        let wide = HealthConfig::for_symbol_bits(4.0, 4);
+        assert_eq!(binary.apt_window, APT_WINDOW_BINARY);
+        assert_eq!(wide.apt_window, APT_WINDOW_NON_BINARY);
+    }
+
+    #[test]
+    fn continuous_tests_pass_on_uniform_counter() {
+        let config = HealthConfig::new(8.0);
+        let mut tests = ContinuousTests::new(&config);
+        let data: Vec<u8> = (0..4096).map(|i| (i % 256) as u8).collect();
+        assert!(tests.check(&data).is_ok());
+    }
+}
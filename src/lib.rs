@@ -26,16 +26,39 @@
 //! # Ok::<(), qcicada::QCicadaError>(())
 //! ```
 
+#[cfg(feature = "async")]
+pub mod async_device;
+pub mod beacon;
 pub mod crypto;
 pub mod device;
 pub mod discovery;
+pub mod entropy;
+pub mod envelope;
+pub mod health;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
 pub mod protocol;
+#[cfg(feature = "daemon")]
+pub mod sink;
+#[cfg(feature = "rand")]
+pub mod rng;
 pub mod serial;
+pub mod stream;
+pub mod trust;
 pub mod types;
 
+#[cfg(feature = "async")]
+pub use async_device::AsyncQCicada;
 pub use device::QCicada;
 pub use discovery::{discover_devices, open_by_serial, probe_device, DiscoveredDevice};
+pub use envelope::{verify_envelope, verify_envelope_signature_only, SignedEnvelope};
+#[cfg(feature = "rand")]
+pub use rng::QCicadaRng;
+#[cfg(feature = "daemon")]
+pub use sink::{EntropyDaemon, EntropySink};
 pub use serial::find_devices;
+pub use stream::RandomStream;
+pub use trust::{Attestation, TrustStore};
 pub use types::*;
 
 /// Errors returned by the qcicada SDK.
@@ -52,4 +75,23 @@ pub enum QCicadaError {
     /// Protocol-level error (unexpected response, parse failure).
     #[error("Protocol error: {0}")]
     Protocol(String),
+
+    /// Signature verification failed or the signature/key was malformed.
+    #[error("Signature error: {0}")]
+    Signature(String),
+
+    /// Device responded with `RESP_NACK` carrying a specific error code.
+    #[error("Device NACK (code {code:#04x})")]
+    Nack {
+        /// The device's one-byte NACK error code.
+        code: u8,
+    },
+
+    /// A host-side continuous health test ([`health::ContinuousTests`]) raised
+    /// an alarm on the device's output stream.
+    #[error("Health test failed at byte offset {offset}")]
+    HealthTestFailed {
+        /// Offset of the offending byte within the read that triggered the alarm.
+        offset: usize,
+    },
 }
@@ -0,0 +1,195 @@
+//! PKCS#11 (Cryptoki) provider exposing a QCicada device as a token.
+//!
+//! Enabled via the `pkcs11` feature. Lets standard tooling (OpenSSL engines,
+//! softhsm-style consumers, TLS stacks) draw entropy and verify device
+//! identity through the uniform Cryptoki interface instead of speaking the
+//! raw serial protocol directly.
+//!
+//! This module implements a minimal single-slot, single-session provider:
+//! one QCicada device is opened lazily on first use and held until
+//! `C_Finalize`. `C_GenerateRandom` routes to [`QCicada::fill_bytes`],
+//! `C_GetTokenInfo` reports the serial/hardware/firmware fields from
+//! [`QCicada::get_info`], and the device certificate/public key surface as a
+//! read-only certificate object that `C_GetAttributeValue` can read.
+
+use std::sync::Mutex;
+
+use pkcs11_bindings::*;
+
+use crate::device::QCicada;
+
+/// The one certificate/public-key object this provider exposes, at a fixed handle.
+const CERTIFICATE_OBJECT_HANDLE: CK_OBJECT_HANDLE = 1;
+
+struct TokenState {
+    device: QCicada,
+    /// Cached on first `attest`-like access so repeated `C_GetAttributeValue`
+    /// calls don't each round-trip to the device.
+    dev_pub_key: Option<Vec<u8>>,
+    dev_certificate: Option<Vec<u8>>,
+}
+
+static TOKEN: Mutex<Option<TokenState>> = Mutex::new(None);
+
+fn with_token(f: impl FnOnce(&mut TokenState) -> CK_RV) -> CK_RV {
+    let mut guard = TOKEN.lock().unwrap();
+    let state = match guard.as_mut() {
+        Some(s) => s,
+        None => match QCicada::open(None, None) {
+            Ok(device) => {
+                *guard = Some(TokenState {
+                    device,
+                    dev_pub_key: None,
+                    dev_certificate: None,
+                });
+                guard.as_mut().unwrap()
+            }
+            Err(_) => return CKR_DEVICE_ERROR,
+        },
+    };
+    f(state)
+}
+
+/// `C_Initialize`: prepares the provider. The device itself is opened lazily
+/// on first real operation so `C_Initialize` can succeed even if no device is
+/// plugged in yet (mirroring how most Cryptoki modules probe slots lazily).
+#[no_mangle]
+pub extern "C" fn C_Initialize(_init_args: CK_VOID_PTR) -> CK_RV {
+    CKR_OK
+}
+
+/// `C_Finalize`: releases the held device, closing the serial connection.
+#[no_mangle]
+pub extern "C" fn C_Finalize(_reserved: CK_VOID_PTR) -> CK_RV {
+    *TOKEN.lock().unwrap() = None;
+    CKR_OK
+}
+
+/// `C_GetTokenInfo`: populate serial/hardware/firmware fields from `get_info`.
+#[no_mangle]
+pub extern "C" fn C_GetTokenInfo(slot_id: CK_SLOT_ID, info: CK_TOKEN_INFO_PTR) -> CK_RV {
+    if slot_id != 0 || info.is_null() {
+        return CKR_SLOT_ID_INVALID;
+    }
+    with_token(|state| {
+        let dev_info = match state.device.get_info() {
+            Ok(i) => i,
+            Err(_) => return CKR_DEVICE_ERROR,
+        };
+        unsafe {
+            let info = &mut *info;
+            write_padded(&mut info.label, b"QCicada QRNG");
+            write_padded(&mut info.manufacturerID, b"Crypta Labs");
+            write_padded(&mut info.model, dev_info.hw_info.as_bytes());
+            write_padded(&mut info.serialNumber, dev_info.serial.as_bytes());
+            info.firmwareVersion.major = (dev_info.fw_version >> 8) as CK_BYTE;
+            info.firmwareVersion.minor = (dev_info.fw_version & 0xff) as CK_BYTE;
+            info.flags = CKF_RNG | CKF_TOKEN_INITIALIZED;
+        }
+        CKR_OK
+    })
+}
+
+/// `C_GenerateRandom`: route to [`QCicada::fill_bytes`].
+#[no_mangle]
+pub extern "C" fn C_GenerateRandom(
+    _session: CK_SESSION_HANDLE,
+    random_data: CK_BYTE_PTR,
+    len: CK_ULONG,
+) -> CK_RV {
+    if random_data.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|state| {
+        let buf = unsafe { std::slice::from_raw_parts_mut(random_data, len as usize) };
+        match state.device.fill_bytes(buf) {
+            Ok(()) => CKR_OK,
+            Err(_) => CKR_DEVICE_ERROR,
+        }
+    })
+}
+
+/// `C_FindObjects`: every call reports the single certificate/public-key
+/// object at [`CERTIFICATE_OBJECT_HANDLE`], without checking whether a device
+/// is attached. This module does not export `C_FindObjectsInit`.
+#[no_mangle]
+pub extern "C" fn C_FindObjects(
+    _session: CK_SESSION_HANDLE,
+    object: CK_OBJECT_HANDLE_PTR,
+    max_object_count: CK_ULONG,
+    object_count: CK_ULONG_PTR,
+) -> CK_RV {
+    if object.is_null() || object_count.is_null() || max_object_count == 0 {
+        return CKR_ARGUMENTS_BAD;
+    }
+    unsafe {
+        *object = CERTIFICATE_OBJECT_HANDLE;
+        *object_count = 1;
+    }
+    CKR_OK
+}
+
+/// `C_GetAttributeValue` for the certificate object: the device's public key
+/// (`CKA_EC_POINT`) and CA certificate / attestation signature (`CKA_VALUE`),
+/// fetched via [`QCicada::get_dev_pub_key`] / [`QCicada::get_dev_certificate`]
+/// and cached for subsequent calls.
+#[no_mangle]
+pub extern "C" fn C_GetAttributeValue(
+    _session: CK_SESSION_HANDLE,
+    object: CK_OBJECT_HANDLE,
+    template: CK_ATTRIBUTE_PTR,
+    count: CK_ULONG,
+) -> CK_RV {
+    if object != CERTIFICATE_OBJECT_HANDLE || template.is_null() {
+        return CKR_OBJECT_HANDLE_INVALID;
+    }
+    with_token(|state| {
+        if state.dev_pub_key.is_none() {
+            state.dev_pub_key = state.device.get_dev_pub_key().ok();
+        }
+        if state.dev_certificate.is_none() {
+            state.dev_certificate = state.device.get_dev_certificate().ok();
+        }
+
+        for i in 0..count as usize {
+            let attr = unsafe { &mut *template.add(i) };
+            let value: Option<&[u8]> = match attr.type_ {
+                CKA_EC_POINT => state.dev_pub_key.as_deref(),
+                CKA_VALUE => state.dev_certificate.as_deref(),
+                _ => continue,
+            };
+            let Some(value) = value else {
+                return CKR_DEVICE_ERROR;
+            };
+            if attr.pValue.is_null() {
+                attr.ulValueLen = value.len() as CK_ULONG;
+            } else if attr.ulValueLen < value.len() as CK_ULONG {
+                // Caller's buffer is smaller than the value: per the PKCS#11
+                // query-then-fetch convention, report the required size and
+                // fail instead of writing past the end of their buffer.
+                attr.ulValueLen = value.len() as CK_ULONG;
+                return CKR_BUFFER_TOO_SMALL;
+            } else {
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        value.as_ptr(),
+                        attr.pValue as *mut u8,
+                        value.len(),
+                    );
+                }
+                attr.ulValueLen = value.len() as CK_ULONG;
+            }
+        }
+        CKR_OK
+    })
+}
+
+/// Right-pad `src` into a fixed-size Cryptoki char array with spaces, per the
+/// PKCS#11 convention for `label`/`manufacturerID`/`model`/`serialNumber`.
+fn write_padded(dest: &mut [CK_UTF8CHAR], src: &[u8]) {
+    let n = src.len().min(dest.len());
+    dest[..n].copy_from_slice(&src[..n].iter().map(|&b| b as CK_UTF8CHAR).collect::<Vec<_>>());
+    for slot in &mut dest[n..] {
+        *slot = b' ' as CK_UTF8CHAR;
+    }
+}
@@ -14,6 +14,7 @@ pub const CMD_SET_CONFIG: u8 = 0x08;
 pub const CMD_GET_STATISTICS: u8 = 0x09;
 pub const CMD_RESET: u8 = 0x0A;
 pub const CMD_GET_INFO: u8 = 0x0B;
+pub const CMD_SIGNED_READ: u8 = 0x0C;
 
 // --- Response codes ---
 pub const RESP_ACK: u8 = 0x11;
@@ -31,6 +32,10 @@ pub const PAYLOAD_INFO: usize = 56;
 // --- Start mode ---
 pub const START_ONE_SHOT: u8 = 0x01;
 
+/// Length in bytes of the 64-byte `r || s` signature appended to a
+/// `SIGNED_READ` response.
+pub const SIGNATURE_LEN: usize = 64;
+
 pub const MAX_BLOCK_SIZE: usize = 4096;
 
 /// Returns the expected success response code for a command.
@@ -44,6 +49,10 @@ pub fn expected_response(cmd: u8) -> Option<u8> {
         CMD_GET_STATISTICS => Some(RESP_STATISTICS),
         CMD_RESET => Some(RESP_ACK),
         CMD_GET_INFO => Some(RESP_INFO),
+        CMD_SIGNED_READ => Some(RESP_ACK),
+        CMD_FW_BEGIN => Some(RESP_ACK),
+        CMD_FW_DATA => Some(RESP_ACK),
+        CMD_FW_END => Some(RESP_ACK),
         _ => None,
     }
 }
@@ -76,6 +85,13 @@ pub fn build_start_one_shot(length: u16) -> Vec<u8> {
     frame
 }
 
+/// Build a SIGNED_READ command requesting `length` bytes of signed random data.
+pub fn build_signed_read(length: u16) -> Vec<u8> {
+    let mut frame = vec![CMD_SIGNED_READ];
+    frame.extend_from_slice(&length.to_le_bytes());
+    frame
+}
+
 /// Parse a 5-byte ACK/status payload.
 pub fn parse_status(data: &[u8]) -> Result<DeviceStatus, QCicadaError> {
     if data.len() < PAYLOAD_ACK {
@@ -120,6 +136,28 @@ pub fn parse_info(data: &[u8]) -> Result<DeviceInfo, QCicadaError> {
     })
 }
 
+/// Parse a `DeviceInfo.hw_info` string like `"CICADA-QRNG-1.1"` into its
+/// `(major, minor)` hardware version, as fed to
+/// [`crypto::verify_certificate`](crate::crypto::verify_certificate).
+///
+/// Returns `None` if the trailing `major.minor` segment isn't present or
+/// doesn't parse as two `u8`s.
+pub fn parse_hw_version(hw_info: &str) -> Option<(u8, u8)> {
+    let version = hw_info.rsplit('-').next()?;
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Parse a `DeviceInfo.serial` string like `"QC0000000217"` into its numeric
+/// serial (e.g. `217`), as fed to
+/// [`crypto::verify_certificate`](crate::crypto::verify_certificate).
+///
+/// Returns `None` if the serial has no trailing digits.
+pub fn parse_serial_int(serial: &str) -> Option<u32> {
+    let digits_start = serial.find(|c: char| c.is_ascii_digit())?;
+    serial[digits_start..].parse().ok()
+}
+
 /// Parse a 12-byte CONFIG response payload.
 pub fn parse_config(data: &[u8]) -> Result<DeviceConfig, QCicadaError> {
     if data.len() < PAYLOAD_CONFIG {
@@ -196,6 +234,223 @@ pub fn checksum8(data: &[u8]) -> u8 {
     !sum
 }
 
+// --- Firmware update ---
+
+pub const CMD_FW_BEGIN: u8 = 0x20;
+pub const CMD_FW_DATA: u8 = 0x21;
+pub const CMD_FW_END: u8 = 0x22;
+
+/// One chunk of a firmware image, bounded by [`MAX_BLOCK_SIZE`] and terminated
+/// with its ones-complement [`checksum8`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirmwareBlock {
+    pub data: Vec<u8>,
+    pub checksum: u8,
+}
+
+/// Split a firmware image into [`MAX_BLOCK_SIZE`]-bounded chunks, each
+/// terminated with its ones-complement [`checksum8`].
+pub fn split_firmware_image(image: &[u8]) -> Vec<FirmwareBlock> {
+    image
+        .chunks(MAX_BLOCK_SIZE)
+        .map(|chunk| FirmwareBlock {
+            data: chunk.to_vec(),
+            checksum: checksum8(chunk),
+        })
+        .collect()
+}
+
+/// Build the `FW_BEGIN` frame announcing a firmware update of `total_len`
+/// bytes, to be followed by one `FW_DATA` frame per block and a final
+/// `FW_END` frame.
+pub fn build_fw_begin(total_len: u32) -> Vec<u8> {
+    let mut frame = vec![CMD_FW_BEGIN];
+    frame.extend_from_slice(&total_len.to_le_bytes());
+    frame
+}
+
+/// Build an `FW_DATA` frame carrying one [`FirmwareBlock`]: the data bytes
+/// followed by their checksum byte.
+pub fn build_fw_data(block: &FirmwareBlock) -> Vec<u8> {
+    let mut frame = vec![CMD_FW_DATA];
+    frame.extend_from_slice(&block.data);
+    frame.push(block.checksum);
+    frame
+}
+
+/// Build the `FW_END` frame closing a firmware update.
+pub fn build_fw_end() -> Vec<u8> {
+    vec![CMD_FW_END]
+}
+
+// --- Incremental streaming decoder ---
+
+/// A fully-parsed device response, as produced by [`Decoder`].
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// `RESP_ACK`: the embedded status payload shared by every ACK (START,
+    /// STOP, RESET, SET_CONFIG, SIGNED_READ, ...).
+    Ack(DeviceStatus),
+    /// `RESP_CONFIG`.
+    Config(DeviceConfig),
+    /// `RESP_STATISTICS`.
+    Statistics(DeviceStatistics),
+    /// `RESP_INFO`.
+    Info(DeviceInfo),
+}
+
+/// Stateful, incremental decoder for the length-prefixed QCC response format.
+///
+/// Unlike `parse_status`/`parse_config`/`parse_statistics`/`parse_info`,
+/// which assume the caller already has a complete payload in hand, `Decoder`
+/// accepts arbitrary byte slices as they arrive from any transport — partial
+/// reads are buffered across calls to [`feed`](Self::feed) until a full
+/// response is available.
+///
+/// `RESP_NACK` is parsed into [`QCicadaError::Nack`] rather than silently
+/// discarded, carrying the device's one-byte error code.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    buf: Vec<u8>,
+}
+
+impl Decoder {
+    /// Create an empty decoder.
+    pub fn new() -> Self {
+        Self { buf: Vec::new() }
+    }
+
+    /// Feed newly-arrived bytes, returning every [`Response`] that became
+    /// complete as a result (zero, one, or several if `bytes` spans multiple
+    /// frames). Returns `Err` as soon as a NACK or malformed frame is
+    /// decoded; bytes after the error remain buffered for the next call.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<Response>, QCicadaError> {
+        self.buf.extend_from_slice(bytes);
+        let mut responses = Vec::new();
+        while let Some(response) = self.try_decode_one()? {
+            responses.push(response);
+        }
+        Ok(responses)
+    }
+
+    fn try_decode_one(&mut self) -> Result<Option<Response>, QCicadaError> {
+        let Some(&code) = self.buf.first() else {
+            return Ok(None);
+        };
+
+        let needed = match code {
+            RESP_NACK => 2, // response code + 1-byte error code
+            _ => 1 + payload_size(code),
+        };
+        if self.buf.len() < needed {
+            return Ok(None);
+        }
+
+        let frame: Vec<u8> = self.buf.drain(..needed).collect();
+        let payload = &frame[1..];
+        match code {
+            RESP_ACK => Ok(Some(Response::Ack(parse_status(payload)?))),
+            RESP_CONFIG => Ok(Some(Response::Config(parse_config(payload)?))),
+            RESP_STATISTICS => Ok(Some(Response::Statistics(parse_statistics(payload)?))),
+            RESP_INFO => Ok(Some(Response::Info(parse_info(payload)?))),
+            RESP_NACK => Err(QCicadaError::Nack { code: payload[0] }),
+            other => Err(QCicadaError::Protocol(format!(
+                "Unknown response code: {other:#04x}"
+            ))),
+        }
+    }
+}
+
+/// Parse a per-block ACK/NACK response to an `FW_DATA` frame.
+///
+/// Returns `Ok(())` on [`RESP_ACK`], `Err` with the NACK reason byte on
+/// [`RESP_NACK`] (0 if the device sent no reason byte), and `Err` on any
+/// other response code.
+pub fn parse_fw_block_response(resp: &[u8]) -> Result<(), QCicadaError> {
+    match resp.first() {
+        Some(&RESP_ACK) => Ok(()),
+        Some(&RESP_NACK) => Err(QCicadaError::Protocol(format!(
+            "Firmware block rejected (NACK code {:#04x})",
+            resp.get(1).copied().unwrap_or(0)
+        ))),
+        Some(&other) => Err(QCicadaError::Protocol(format!(
+            "Unexpected firmware block response: {other:#04x}"
+        ))),
+        None => Err(QCicadaError::Protocol(
+            "Empty firmware block response".into(),
+        )),
+    }
+}
+
+// --- COBS framing ---
+//
+// The length-prefixed format above permanently desynchronizes if a byte is
+// dropped or a stale continuous-mode burst leaks into the buffer — which is
+// exactly why `SerialTransport::open` has to drain the port on connect.
+// Consistent Overhead Byte Stuffing avoids that: `0x00` never appears inside
+// an encoded frame, so a reader that loses sync can discard bytes up to the
+// next `0x00` and resynchronize on the following complete frame.
+
+/// COBS-encode `data` into one wire frame, including the trailing `0x00`
+/// delimiter. Pair with [`decode_frame`] on the receiving end and
+/// [`SerialTransport::read_frame`](crate::serial::SerialTransport::read_frame)
+/// to read one back.
+pub fn encode_frame(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 254 + 2);
+    let mut code_pos = out.len();
+    out.push(0); // placeholder, patched below
+    let mut code = 1u8;
+
+    for &byte in data {
+        if byte == 0 {
+            out[code_pos] = code;
+            code_pos = out.len();
+            out.push(0);
+            code = 1;
+        } else {
+            out.push(byte);
+            code += 1;
+            if code == 0xff {
+                out[code_pos] = code;
+                code_pos = out.len();
+                out.push(0);
+                code = 1;
+            }
+        }
+    }
+    out[code_pos] = code;
+    out.push(0x00);
+    out
+}
+
+/// Decode one COBS frame's payload. `encoded` must *not* include the
+/// trailing `0x00` delimiter — strip it first (as
+/// [`SerialTransport::read_frame`](crate::serial::SerialTransport::read_frame)
+/// does when it finds one).
+pub fn decode_frame(encoded: &[u8]) -> Result<Vec<u8>, QCicadaError> {
+    let mut out = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        let code = encoded[i] as usize;
+        if code == 0 {
+            return Err(QCicadaError::Protocol(
+                "COBS: zero byte in code position".into(),
+            ));
+        }
+        i += 1;
+        let end = i + (code - 1);
+        if end > encoded.len() {
+            return Err(QCicadaError::Protocol("COBS: truncated frame".into()));
+        }
+        out.extend_from_slice(&encoded[i..end]);
+        i = end;
+        if code != 0xff && i < encoded.len() {
+            out.push(0);
+        }
+    }
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +492,14 @@ mod tests {
         assert_eq!(u16::from_le_bytes([frame[2], frame[3]]), 65535);
     }
 
+    #[test]
+    fn build_signed_read_format() {
+        let frame = build_signed_read(32);
+        assert_eq!(frame[0], CMD_SIGNED_READ);
+        assert_eq!(u16::from_le_bytes([frame[1], frame[2]]), 32);
+        assert_eq!(frame.len(), 3);
+    }
+
     // -- Response mapping tests --
 
     #[test]
@@ -249,6 +512,7 @@ mod tests {
         assert_eq!(expected_response(CMD_GET_STATISTICS), Some(RESP_STATISTICS));
         assert_eq!(expected_response(CMD_RESET), Some(RESP_ACK));
         assert_eq!(expected_response(CMD_GET_INFO), Some(RESP_INFO));
+        assert_eq!(expected_response(CMD_SIGNED_READ), Some(RESP_ACK));
         assert_eq!(expected_response(0xFF), None);
     }
 
@@ -349,6 +613,33 @@ mod tests {
         assert!(parse_info(&[0u8; 10]).is_err());
     }
 
+    // -- hw_version / serial parsing --
+
+    #[test]
+    fn parse_hw_version_normal() {
+        assert_eq!(parse_hw_version("CICADA-QRNG-1.1"), Some((1, 1)));
+        assert_eq!(parse_hw_version("CICADA-QRNG-2.10"), Some((2, 10)));
+    }
+
+    #[test]
+    fn parse_hw_version_malformed() {
+        assert_eq!(parse_hw_version("CICADA-QRNG"), None);
+        assert_eq!(parse_hw_version(""), None);
+        assert_eq!(parse_hw_version("CICADA-QRNG-x.y"), None);
+    }
+
+    #[test]
+    fn parse_serial_int_normal() {
+        assert_eq!(parse_serial_int("QC0000000217"), Some(217));
+        assert_eq!(parse_serial_int("QC0"), Some(0));
+    }
+
+    #[test]
+    fn parse_serial_int_malformed() {
+        assert_eq!(parse_serial_int("QC"), None);
+        assert_eq!(parse_serial_int(""), None);
+    }
+
     // -- Config parse/serialize roundtrip tests --
 
     fn make_config_payload(pp: u8, level: f32, flags: u8, n_lsb: u8, hash_in: u8, blk: u16, ac: u16) -> Vec<u8> {
@@ -529,6 +820,134 @@ mod tests {
         assert_eq!(checksum8(&[0xFF, 0x01]), 0xFF);
     }
 
+    // -- Incremental decoder tests --
+
+    #[test]
+    fn decoder_waits_for_full_frame() {
+        let mut decoder = Decoder::new();
+        let status = [0x01, 0x40, 0x34, 0x00, 0x00];
+        let responses = decoder.feed(&[RESP_ACK]).unwrap();
+        assert!(responses.is_empty());
+        let responses = decoder.feed(&status[..3]).unwrap();
+        assert!(responses.is_empty());
+        let responses = decoder.feed(&status[3..]).unwrap();
+        assert_eq!(responses.len(), 1);
+        match &responses[0] {
+            Response::Ack(s) => assert_eq!(s.ready_bytes, 13376),
+            other => panic!("expected Ack, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decoder_decodes_multiple_frames_in_one_feed() {
+        let mut decoder = Decoder::new();
+        let mut bytes = vec![RESP_ACK];
+        bytes.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, 0x00]);
+        bytes.push(RESP_ACK);
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00]);
+        let responses = decoder.feed(&bytes).unwrap();
+        assert_eq!(responses.len(), 2);
+    }
+
+    #[test]
+    fn decoder_errors_on_nack_with_reason() {
+        let mut decoder = Decoder::new();
+        let err = decoder.feed(&[RESP_NACK, 0x03]).unwrap_err();
+        match err {
+            QCicadaError::Nack { code } => assert_eq!(code, 0x03),
+            other => panic!("expected Nack, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decoder_decodes_typed_responses() {
+        let mut decoder = Decoder::new();
+        let mut bytes = vec![RESP_CONFIG];
+        bytes.extend_from_slice(&make_config_payload(0, 0.5, 0, 4, 64, 448, 2048));
+        let responses = decoder.feed(&bytes).unwrap();
+        assert_eq!(responses.len(), 1);
+        assert!(matches!(responses[0], Response::Config(_)));
+    }
+
+    #[test]
+    fn decoder_resumes_after_nack() {
+        let mut decoder = Decoder::new();
+        assert!(decoder.feed(&[RESP_NACK, 0x01]).is_err());
+        let mut bytes = vec![RESP_ACK];
+        bytes.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, 0x00]);
+        let responses = decoder.feed(&bytes).unwrap();
+        assert_eq!(responses.len(), 1);
+    }
+
+    // -- Firmware update tests --
+
+    #[test]
+    fn split_firmware_image_chunks_and_checksums() {
+        let image = vec![0xAAu8; MAX_BLOCK_SIZE + 10];
+        let blocks = split_firmware_image(&image);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].data.len(), MAX_BLOCK_SIZE);
+        assert_eq!(blocks[1].data.len(), 10);
+        assert_eq!(blocks[0].checksum, checksum8(&blocks[0].data));
+        assert_eq!(blocks[1].checksum, checksum8(&blocks[1].data));
+    }
+
+    #[test]
+    fn split_firmware_image_empty() {
+        assert!(split_firmware_image(&[]).is_empty());
+    }
+
+    #[test]
+    fn build_fw_data_appends_checksum() {
+        let block = FirmwareBlock {
+            data: vec![0x01, 0x02, 0x03],
+            checksum: checksum8(&[0x01, 0x02, 0x03]),
+        };
+        let frame = build_fw_data(&block);
+        assert_eq!(frame[0], CMD_FW_DATA);
+        assert_eq!(&frame[1..4], &[0x01, 0x02, 0x03]);
+        assert_eq!(frame[4], block.checksum);
+    }
+
+    #[test]
+    fn build_fw_begin_encodes_length() {
+        let frame = build_fw_begin(0x0001_0203);
+        assert_eq!(frame[0], CMD_FW_BEGIN);
+        assert_eq!(
+            u32::from_le_bytes(frame[1..5].try_into().unwrap()),
+            0x0001_0203
+        );
+    }
+
+    #[test]
+    fn build_fw_end_is_bare_command() {
+        assert_eq!(build_fw_end(), vec![CMD_FW_END]);
+    }
+
+    #[test]
+    fn parse_fw_block_response_ack() {
+        assert!(parse_fw_block_response(&[RESP_ACK]).is_ok());
+    }
+
+    #[test]
+    fn parse_fw_block_response_nack_with_reason() {
+        let err = parse_fw_block_response(&[RESP_NACK, 0x07]).unwrap_err();
+        assert!(err.to_string().contains("0x07"));
+    }
+
+    #[test]
+    fn parse_fw_block_response_unexpected() {
+        assert!(parse_fw_block_response(&[0xFF]).is_err());
+        assert!(parse_fw_block_response(&[]).is_err());
+    }
+
+    #[test]
+    fn fw_command_codes_get_expected_response() {
+        assert_eq!(expected_response(CMD_FW_BEGIN), Some(RESP_ACK));
+        assert_eq!(expected_response(CMD_FW_DATA), Some(RESP_ACK));
+        assert_eq!(expected_response(CMD_FW_END), Some(RESP_ACK));
+    }
+
     // -- PostProcess enum tests --
 
     #[test]
@@ -559,6 +978,7 @@ mod tests {
         assert_eq!(CMD_GET_STATISTICS, 0x09);
         assert_eq!(CMD_RESET, 0x0A);
         assert_eq!(CMD_GET_INFO, 0x0B);
+        assert_eq!(CMD_SIGNED_READ, 0x0C);
     }
 
     #[test]
@@ -569,4 +989,47 @@ mod tests {
         assert_eq!(RESP_STATISTICS, 0x19);
         assert_eq!(RESP_INFO, 0x1B);
     }
+
+    // -- COBS framing --
+
+    #[test]
+    fn encode_frame_never_contains_interior_zero() {
+        let data = [1, 0, 2, 0, 0, 3, 0];
+        let frame = encode_frame(&data);
+        assert_eq!(frame.last(), Some(&0x00));
+        assert!(!frame[..frame.len() - 1].contains(&0x00));
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        for data in [
+            &b""[..],
+            &b"hi"[..],
+            &[0u8; 1][..],
+            &[0u8, 0u8, 0u8][..],
+            &(0u8..=255).collect::<Vec<u8>>()[..],
+        ] {
+            let frame = encode_frame(data);
+            let encoded = &frame[..frame.len() - 1]; // strip delimiter
+            assert_eq!(decode_frame(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn encode_decode_roundtrip_across_254_byte_boundary() {
+        let data: Vec<u8> = (0..600).map(|i| (i % 251) as u8 + 1).collect();
+        let frame = encode_frame(&data);
+        let encoded = &frame[..frame.len() - 1];
+        assert_eq!(decode_frame(encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn decode_frame_rejects_truncated_data() {
+        assert!(decode_frame(&[5, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn decode_frame_rejects_zero_in_code_position() {
+        assert!(decode_frame(&[1, 0, 1]).is_err());
+    }
 }
@@ -0,0 +1,133 @@
+//! `rand_core` integration backed by an internally buffered entropy pool.
+//!
+//! Enabled via the `rand` feature. [`QCicada::random`] and the `io::Read` impl
+//! both force a full serial round trip — including the protocol's 500ms+
+//! timeout floor — for every draw, however small. [`QCicadaRng`] instead
+//! refills a buffer in protocol-sized chunks and serves small draws out of it
+//! locally, so it can back [`rand::distributions`], key generation, or
+//! anything else expecting an [`RngCore`].
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+use crate::device::QCicada;
+
+/// Default size of the internal buffer refilled from the device, in bytes.
+const DEFAULT_BUFFER_SIZE: usize = 4096;
+
+/// An [`RngCore`] + [`CryptoRng`] wrapper around [`QCicada`] backed by a
+/// buffered entropy pool.
+///
+/// Draws smaller than the buffer are served out of memory; the buffer is
+/// refilled via [`QCicada::random`] in `buffer_size`-byte chunks (at most
+/// 65535 bytes, the protocol limit) once drained.
+pub struct QCicadaRng {
+    device: QCicada,
+    buffer: Vec<u8>,
+    /// Index of the next unconsumed byte in `buffer`.
+    cursor: usize,
+    buffer_size: usize,
+}
+
+impl QCicadaRng {
+    /// Wrap a device with the default buffer size (4096 bytes).
+    pub fn new(device: QCicada) -> Self {
+        Self::with_buffer_size(device, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Wrap a device with a custom refill chunk size.
+    pub fn with_buffer_size(device: QCicada, buffer_size: usize) -> Self {
+        Self {
+            device,
+            buffer: Vec::new(),
+            cursor: 0,
+            buffer_size,
+        }
+    }
+
+    /// Consume the wrapper, returning the underlying device.
+    pub fn into_inner(self) -> QCicada {
+        self.device
+    }
+
+    fn refill(&mut self) -> Result<(), Error> {
+        let n = self.buffer_size.min(u16::MAX as usize) as u16;
+        let fresh = self
+            .device
+            .random(n)
+            .map_err(|e| Error::new(Box::new(e)))?;
+        self.buffer = fresh;
+        self.cursor = 0;
+        Ok(())
+    }
+
+    fn fill_from_buffer(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.cursor >= self.buffer.len() {
+                self.refill()?;
+            }
+            let available = &self.buffer[self.cursor..];
+            let take = available.len().min(dest.len() - filled);
+            dest[filled..filled + take].copy_from_slice(&available[..take]);
+            self.cursor += take;
+            filled += take;
+        }
+        Ok(())
+    }
+}
+
+impl RngCore for QCicadaRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("QCicadaRng: device read failed");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_from_buffer(dest)
+    }
+}
+
+impl CryptoRng for QCicadaRng {}
+
+/// Direct [`RngCore`] + [`CryptoRng`] impl for [`QCicada`] itself, for callers
+/// who want `impl RngCore + CryptoRng` without [`QCicadaRng`]'s buffering
+/// (e.g. `SigningKey::random(&mut qrng)`-style key generation, where each
+/// draw is small and buffering adds no value). `next_u32`/`next_u64` build on
+/// [`QCicada::fill_bytes`]; `try_fill_bytes` maps serial/timeout failures to a
+/// real [`Error`] instead of panicking.
+impl RngCore for QCicada {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        RngCore::fill_bytes(self, &mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        RngCore::fill_bytes(self, &mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("QCicada: device read failed");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        QCicada::fill_bytes(self, dest).map_err(|e| Error::new(Box::new(e)))
+    }
+}
+
+impl CryptoRng for QCicada {}
@@ -1,49 +1,129 @@
 //! Platform-aware serial transport for QCicada QRNG devices.
 
-use serialport::SerialPort;
+use serialport::{SerialPort, SerialPortType};
 use std::io::{Read, Write};
 use std::time::Duration;
 
 use crate::QCicadaError;
 
-/// Auto-discover QCicada QRNG devices by serial port pattern.
-pub fn find_devices() -> Vec<String> {
-    let pattern = if cfg!(target_os = "macos") {
-        "/dev/cu.usbserial-"
-    } else {
-        "/dev/ttyUSB"
-    };
+/// FTDI's USB vendor ID. QCicada devices enumerate as FTDI USB-serial
+/// adapters, so Windows (where port names are opaque `COM3`-style handles)
+/// discovers devices by VID rather than by name.
+const FTDI_VID: u16 = 0x0403;
 
+/// Auto-discover QCicada QRNG devices.
+///
+/// macOS/Linux/FreeBSD match on the platform's usual USB-serial device node
+/// prefix; Windows has no such naming convention, so it filters
+/// `available_ports()` by FTDI USB vendor ID instead.
+pub fn find_devices() -> Vec<String> {
     let mut devices: Vec<String> = serialport::available_ports()
         .unwrap_or_default()
         .into_iter()
+        .filter(is_qcicada_port)
         .map(|p| p.port_name)
-        .filter(|name| name.starts_with(pattern))
         .collect();
     devices.sort();
     devices
 }
 
-/// Serial transport with macOS FTDI workarounds.
-///
-/// On macOS:
-/// - Minimum 500ms read timeout (FTDI driver needs it)
-/// - Flush + 50ms delay after every write
-///
-/// On Linux:
-/// - Standard serial behavior
+fn is_qcicada_port(port: &serialport::SerialPortInfo) -> bool {
+    if cfg!(target_os = "windows") {
+        matches!(&port.port_type, SerialPortType::UsbPort(usb) if usb.vid == FTDI_VID)
+    } else if cfg!(target_os = "macos") {
+        port.port_name.starts_with("/dev/cu.usbserial-")
+    } else if cfg!(target_os = "freebsd") {
+        port.port_name.starts_with("/dev/cuaU")
+    } else {
+        port.port_name.starts_with("/dev/ttyUSB")
+    }
+}
+
+/// Per-platform serial transport quirks, as data rather than scattered
+/// `cfg!`/bool branches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Platform {
+    /// Extra delay after every write, before the device is assumed ready for
+    /// the next command (FTDI driver latency).
+    pub write_flush_delay: Duration,
+    /// Floor applied to every caller-requested read timeout.
+    pub min_read_timeout: Duration,
+    /// Whether [`SerialTransport::open`] should drain and discard any
+    /// leftover input after the initial stop-continuous-mode write (FTDI
+    /// buffers can hold a burst of stale continuous-mode data).
+    pub drain_on_open: bool,
+}
+
+impl Platform {
+    /// macOS: FTDI's driver needs a 500ms read-timeout floor and a 50ms
+    /// post-write settle delay, and benefits from draining stale input on open.
+    pub const fn macos() -> Self {
+        Self {
+            write_flush_delay: Duration::from_millis(50),
+            min_read_timeout: Duration::from_millis(500),
+            drain_on_open: true,
+        }
+    }
+
+    /// Linux/FreeBSD: the kernel's FTDI driver needs no extra settling time.
+    pub const fn unix_like() -> Self {
+        Self {
+            write_flush_delay: Duration::ZERO,
+            min_read_timeout: Duration::ZERO,
+            drain_on_open: false,
+        }
+    }
+
+    /// Windows: same timing as other non-macOS platforms; VID/PID-based
+    /// discovery (see [`find_devices`]) is the only Windows-specific quirk.
+    pub const fn windows() -> Self {
+        Self::unix_like()
+    }
+
+    /// The quirk set for the platform this binary was compiled for.
+    pub const fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::macos()
+        } else if cfg!(target_os = "windows") {
+            Self::windows()
+        } else {
+            Self::unix_like()
+        }
+    }
+}
+
+/// Serial transport with per-platform FTDI workarounds (see [`Platform`]).
 pub struct SerialTransport {
     port: Box<dyn SerialPort>,
-    is_macos: bool,
+    platform: Platform,
+    /// Currently configured read timeout. `None` means block indefinitely —
+    /// tracked so [`read_exact`](Self::read_exact) knows whether a `TimedOut`
+    /// from the underlying port is a real deadline or just noise to retry
+    /// past.
+    current_timeout: Option<Duration>,
 }
 
-const MIN_TIMEOUT_MACOS: Duration = Duration::from_millis(500);
+/// Timeout value handed to the underlying `serialport` port when the caller
+/// requests indefinite blocking via `None`. `serialport` has no native
+/// "block forever" timeout, so this stands in for it; [`SerialTransport`]
+/// itself tracks the `None` intent separately via `current_timeout`.
+const EFFECTIVELY_FOREVER: Duration = Duration::from_secs(u64::MAX / 2);
 
 impl SerialTransport {
-    /// Open a serial connection to the given port.
+    /// Open a serial connection to the given port, applying
+    /// [`Platform::current`]'s quirks.
     pub fn open(port_name: &str, timeout: Duration) -> Result<Self, QCicadaError> {
-        let is_macos = cfg!(target_os = "macos");
+        Self::open_with_platform(port_name, timeout, Platform::current())
+    }
 
+    /// Open a serial connection to the given port under an explicit
+    /// [`Platform`] descriptor, bypassing autodetection (useful for testing
+    /// a non-native platform's quirks).
+    pub fn open_with_platform(
+        port_name: &str,
+        timeout: Duration,
+        platform: Platform,
+    ) -> Result<Self, QCicadaError> {
         let mut port = serialport::new(port_name, 1_000_000)
             .timeout(timeout)
             .open()
@@ -54,44 +134,52 @@ impl SerialTransport {
             .map_err(|e| QCicadaError::Serial(format!("Init write failed: {e}")))?;
         std::thread::sleep(Duration::from_millis(500));
 
-        port.set_timeout(Duration::from_millis(300))
-            .map_err(|e| QCicadaError::Serial(format!("Set timeout failed: {e}")))?;
+        if platform.drain_on_open {
+            port.set_timeout(Duration::from_millis(300))
+                .map_err(|e| QCicadaError::Serial(format!("Set timeout failed: {e}")))?;
 
-        let mut drain = [0u8; 4096];
-        loop {
-            match port.read(&mut drain) {
-                Ok(0) => break,
-                Err(_) => break,
-                Ok(_) => continue,
+            let mut drain = [0u8; 4096];
+            loop {
+                match port.read(&mut drain) {
+                    Ok(0) => break,
+                    Err(_) => break,
+                    Ok(_) => continue,
+                }
             }
         }
 
         port.clear(serialport::ClearBuffer::Input)
             .map_err(|e| QCicadaError::Serial(format!("Clear buffer failed: {e}")))?;
 
-        port.set_timeout(timeout)
+        port.set_timeout(timeout.max(platform.min_read_timeout))
             .map_err(|e| QCicadaError::Serial(format!("Set timeout failed: {e}")))?;
 
         std::thread::sleep(Duration::from_millis(100));
 
-        Ok(Self { port, is_macos })
+        Ok(Self {
+            port,
+            platform,
+            current_timeout: Some(timeout),
+        })
     }
 
-    /// Write data. On macOS, flushes and waits for FTDI latency.
+    /// Write data, then wait out [`Platform::write_flush_delay`].
     pub fn write(&mut self, data: &[u8]) -> Result<(), QCicadaError> {
         self.port
             .write_all(data)
             .map_err(|e| QCicadaError::Serial(format!("Write failed: {e}")))?;
-        if self.is_macos {
+        if !self.platform.write_flush_delay.is_zero() {
             self.port
                 .flush()
                 .map_err(|e| QCicadaError::Serial(format!("Flush failed: {e}")))?;
-            std::thread::sleep(Duration::from_millis(50));
+            std::thread::sleep(self.platform.write_flush_delay);
         }
         Ok(())
     }
 
-    /// Read exactly `len` bytes (returns fewer on timeout).
+    /// Read up to `len` bytes, returning fewer if the configured timeout
+    /// elapses first. See [`read_exact`](Self::read_exact) to instead fail
+    /// loudly on a short read.
     pub fn read(&mut self, len: usize) -> Result<Vec<u8>, QCicadaError> {
         let mut buf = vec![0u8; len];
         let mut total = 0;
@@ -107,6 +195,37 @@ impl SerialTransport {
         Ok(buf)
     }
 
+    /// Read exactly `len` bytes, retrying past `TimedOut` until they all
+    /// arrive if the configured timeout is `None` (block indefinitely).
+    ///
+    /// Returns [`QCicadaError::Serial`] if the port closes or — with a
+    /// concrete configured timeout — it elapses before `len` bytes are
+    /// collected, instead of silently truncating like [`read`](Self::read).
+    pub fn read_exact(&mut self, len: usize) -> Result<Vec<u8>, QCicadaError> {
+        let mut buf = vec![0u8; len];
+        let mut total = 0;
+        while total < len {
+            match self.port.read(&mut buf[total..]) {
+                Ok(0) => {
+                    return Err(QCicadaError::Serial(format!(
+                        "Port closed after {total}/{len} bytes"
+                    )))
+                }
+                Ok(n) => total += n,
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                    if self.current_timeout.is_none() {
+                        continue;
+                    }
+                    return Err(QCicadaError::Serial(format!(
+                        "Timed out after {total}/{len} bytes"
+                    )));
+                }
+                Err(e) => return Err(QCicadaError::Serial(format!("Read failed: {e}"))),
+            }
+        }
+        Ok(buf)
+    }
+
     /// Flush output and clear input buffer.
     pub fn flush(&mut self) -> Result<(), QCicadaError> {
         self.port
@@ -118,16 +237,94 @@ impl SerialTransport {
         Ok(())
     }
 
-    /// Set read timeout, enforcing macOS minimum.
-    pub fn set_timeout(&mut self, timeout: Duration) -> Result<(), QCicadaError> {
-        let timeout = if self.is_macos {
-            timeout.max(MIN_TIMEOUT_MACOS)
-        } else {
-            timeout
+    /// Set the read timeout. `None` means block indefinitely until the
+    /// requested length arrives — the platform's
+    /// [`Platform::min_read_timeout`] floor only applies when a concrete
+    /// duration is supplied.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), QCicadaError> {
+        let effective = match timeout {
+            Some(t) => t.max(self.platform.min_read_timeout),
+            None => EFFECTIVELY_FOREVER,
         };
         self.port
-            .set_timeout(timeout)
+            .set_timeout(effective)
             .map_err(|e| QCicadaError::Serial(format!("Set timeout failed: {e}")))?;
+        self.current_timeout = timeout;
         Ok(())
     }
+
+    /// Read one COBS-framed message (see [`crate::protocol::encode_frame`]/
+    /// [`decode_frame`](crate::protocol::decode_frame)): accumulate bytes
+    /// until the `0x00` delimiter, then decode them.
+    ///
+    /// A lost byte or leftover continuous-mode noise can never desynchronize
+    /// this past one frame — `0x00` never appears inside an encoded frame.
+    /// Garbage before the next delimiter is read as part of the current
+    /// frame, so that call returns a decode error or a corrupt payload; the
+    /// next call starts fresh after the delimiter.
+    pub fn read_frame(&mut self) -> Result<Vec<u8>, QCicadaError> {
+        let mut encoded = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            match self.port.read(&mut byte) {
+                Ok(0) => {
+                    return Err(QCicadaError::Serial(
+                        "Port closed while reading a framed message".into(),
+                    ))
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                    return Err(QCicadaError::Serial(
+                        "Timed out waiting for frame delimiter".into(),
+                    ))
+                }
+                Err(e) => return Err(QCicadaError::Serial(format!("Read failed: {e}"))),
+            }
+
+            if byte[0] == 0x00 {
+                if encoded.is_empty() {
+                    // Stray/leading delimiter (e.g. from resync) — keep waiting.
+                    continue;
+                }
+                return crate::protocol::decode_frame(&encoded);
+            }
+            encoded.push(byte[0]);
+        }
+    }
+
+    /// Clone the underlying port handle for a background reader thread (see
+    /// [`crate::stream`]). Internal — the clone bypasses this transport's
+    /// bookkeeping, so it's only safe to use for the dedicated continuous-mode
+    /// reader, which owns it exclusively.
+    pub(crate) fn try_clone_port(&self) -> Result<Box<dyn SerialPort>, QCicadaError> {
+        self.port
+            .try_clone()
+            .map_err(|e| QCicadaError::Serial(format!("Failed to clone port handle: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macos_platform_has_nonzero_quirks() {
+        let p = Platform::macos();
+        assert!(!p.write_flush_delay.is_zero());
+        assert!(!p.min_read_timeout.is_zero());
+        assert!(p.drain_on_open);
+    }
+
+    #[test]
+    fn unix_like_platform_has_no_quirks() {
+        let p = Platform::unix_like();
+        assert!(p.write_flush_delay.is_zero());
+        assert!(p.min_read_timeout.is_zero());
+        assert!(!p.drain_on_open);
+    }
+
+    #[test]
+    fn windows_platform_matches_unix_like_timing() {
+        assert_eq!(Platform::windows(), Platform::unix_like());
+    }
 }
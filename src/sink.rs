@@ -0,0 +1,216 @@
+//! Entropy-sink subsystem: feed a device's continuous-mode stream into OS
+//! entropy pools, files, or named pipes, so the crate can run as a
+//! long-lived entropy provider rather than only returning `Vec<u8>` to a
+//! caller.
+//!
+//! Enabled via the `daemon` feature. Built entirely on top of
+//! [`RandomStream`](crate::stream::RandomStream), so the `device`/`serial`
+//! layers stay unaware of the destination.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use crate::stream::RandomStream;
+use crate::types::PostProcess;
+use crate::QCicadaError;
+
+/// Size of each chunk pulled from the stream per write/submit call.
+const FEED_CHUNK: usize = 4096;
+
+/// Destination for harvested entropy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntropySink {
+    /// Discard everything — useful for exercising the stream alone.
+    Off,
+    /// Append continuously to a regular file.
+    File(PathBuf),
+    /// Write continuously to a named pipe, which must already exist (e.g.
+    /// created with `mkfifo(1)`).
+    Fifo(PathBuf),
+    /// Submit to the Linux kernel entropy pool via `/dev/random`'s
+    /// `RNDADDENTROPY` ioctl. Linux-only; [`EntropyDaemon::run`] errors on
+    /// other platforms.
+    KernelPool,
+}
+
+/// Feeds a [`RandomStream`] into an [`EntropySink`] until the stream ends
+/// (the reader thread stops) or a write/submit fails.
+pub struct EntropyDaemon {
+    sink: EntropySink,
+    postprocess: PostProcess,
+}
+
+impl EntropyDaemon {
+    /// `postprocess` must match the device's configured
+    /// [`PostProcess`] mode, so [`EntropySink::KernelPool`] can credit
+    /// entropy conservatively for raw (unconditioned) modes.
+    pub fn new(sink: EntropySink, postprocess: PostProcess) -> Self {
+        Self { sink, postprocess }
+    }
+
+    /// Run the feed loop, blocking until `stream` ends or the sink rejects a
+    /// write.
+    pub fn run(&self, stream: &mut RandomStream) -> Result<(), QCicadaError> {
+        match &self.sink {
+            EntropySink::Off => {
+                let mut buf = [0u8; FEED_CHUNK];
+                while self.read_chunk(stream, &mut buf)? > 0 {}
+                Ok(())
+            }
+            EntropySink::File(path) => {
+                let writer = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| {
+                        QCicadaError::Protocol(format!(
+                            "Failed to open sink file {}: {e}",
+                            path.display()
+                        ))
+                    })?;
+                self.feed_writer(stream, writer)
+            }
+            EntropySink::Fifo(path) => {
+                let writer = OpenOptions::new().write(true).open(path).map_err(|e| {
+                    QCicadaError::Protocol(format!(
+                        "Failed to open sink fifo {}: {e}",
+                        path.display()
+                    ))
+                })?;
+                self.feed_writer(stream, writer)
+            }
+            EntropySink::KernelPool => self.feed_kernel_pool(stream),
+        }
+    }
+
+    fn read_chunk(&self, stream: &mut RandomStream, buf: &mut [u8]) -> Result<usize, QCicadaError> {
+        stream
+            .read(buf)
+            .map_err(|e| QCicadaError::Serial(format!("Stream read failed: {e}")))
+    }
+
+    fn feed_writer(&self, stream: &mut RandomStream, mut writer: impl Write) -> Result<(), QCicadaError> {
+        let mut buf = [0u8; FEED_CHUNK];
+        loop {
+            let n = self.read_chunk(stream, &mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            writer
+                .write_all(&buf[..n])
+                .map_err(|e| QCicadaError::Protocol(format!("Sink write failed: {e}")))?;
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn feed_kernel_pool(&self, stream: &mut RandomStream) -> Result<(), QCicadaError> {
+        let mut buf = [0u8; FEED_CHUNK];
+        loop {
+            let n = self.read_chunk(stream, &mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            kernel_pool::add_entropy(&buf[..n], entropy_credit_bits(self.postprocess, n))?;
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn feed_kernel_pool(&self, _stream: &mut RandomStream) -> Result<(), QCicadaError> {
+        Err(QCicadaError::Protocol(
+            "EntropySink::KernelPool requires Linux".into(),
+        ))
+    }
+}
+
+/// Conservative entropy credit in bits for `byte_len` harvested bytes under
+/// `postprocess`: full credit for SHA256-conditioned output (already
+/// whitened, one-way-function output), reduced credit for raw (unconditioned)
+/// modes where each bit doesn't carry a full bit of min-entropy.
+fn entropy_credit_bits(postprocess: PostProcess, byte_len: usize) -> u32 {
+    let full_bits = (byte_len * 8) as u32;
+    match postprocess {
+        PostProcess::Sha256 => full_bits,
+        PostProcess::RawNoise => full_bits / 2,
+        PostProcess::RawSamples => full_bits / 4,
+    }
+}
+
+/// Linux `RNDADDENTROPY` plumbing, isolated so the rest of the module stays
+/// platform-agnostic.
+#[cfg(target_os = "linux")]
+mod kernel_pool {
+    use std::fs::OpenOptions;
+    use std::os::unix::io::AsRawFd;
+
+    use crate::QCicadaError;
+
+    /// `RNDADDENTROPY` from `linux/random.h`: `_IOW('R', 0x03, int[2])`. The
+    /// ioctl's encoded size only covers the fixed `rand_pool_info` header
+    /// (`entropy_count`, `buf_size`); the trailing `buf` is a kernel-side
+    /// flexible array member sized by `buf_size`, not part of the ioctl number.
+    const RNDADDENTROPY: libc::c_ulong = 0x4008_5203;
+
+    #[repr(C)]
+    struct RandPoolInfoHeader {
+        entropy_count: libc::c_int,
+        buf_size: libc::c_int,
+    }
+
+    /// Submit `data` to the kernel entropy pool via `/dev/random`, crediting
+    /// `entropy_bits` bits of entropy.
+    pub(super) fn add_entropy(data: &[u8], entropy_bits: u32) -> Result<(), QCicadaError> {
+        let file = OpenOptions::new()
+            .write(true)
+            .open("/dev/random")
+            .map_err(|e| QCicadaError::Protocol(format!("Failed to open /dev/random: {e}")))?;
+
+        let header = RandPoolInfoHeader {
+            entropy_count: entropy_bits as libc::c_int,
+            buf_size: data.len() as libc::c_int,
+        };
+        let mut payload = Vec::with_capacity(std::mem::size_of::<RandPoolInfoHeader>() + data.len());
+        // SAFETY: RandPoolInfoHeader is a `#[repr(C)]` struct of two `c_int`s
+        // with no padding or invalid bit patterns — reading it as bytes is
+        // always sound, matching the kernel's `struct rand_pool_info` layout.
+        payload.extend_from_slice(unsafe {
+            std::slice::from_raw_parts(
+                &header as *const RandPoolInfoHeader as *const u8,
+                std::mem::size_of::<RandPoolInfoHeader>(),
+            )
+        });
+        payload.extend_from_slice(data);
+
+        // SAFETY: `payload` outlives the call, and its layout matches what
+        // RNDADDENTROPY expects (header followed by `buf_size` data bytes).
+        let ret = unsafe { libc::ioctl(file.as_raw_fd(), RNDADDENTROPY, payload.as_ptr()) };
+        if ret < 0 {
+            return Err(QCicadaError::Protocol(format!(
+                "RNDADDENTROPY ioctl failed: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy_credit_full_for_sha256() {
+        assert_eq!(entropy_credit_bits(PostProcess::Sha256, 32), 256);
+    }
+
+    #[test]
+    fn entropy_credit_reduced_for_raw_modes() {
+        assert_eq!(entropy_credit_bits(PostProcess::RawNoise, 32), 128);
+        assert_eq!(entropy_credit_bits(PostProcess::RawSamples, 32), 64);
+    }
+
+    #[test]
+    fn off_sink_is_distinct_from_kernel_pool() {
+        assert_ne!(EntropySink::Off, EntropySink::KernelPool);
+    }
+}
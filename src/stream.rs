@@ -0,0 +1,143 @@
+//! Background-thread streaming reader for continuous mode.
+//!
+//! [`SerialTransport::read`](crate::serial::SerialTransport::read) blocks the
+//! calling thread for every chunk, which ties a consumer's throughput to FTDI
+//! read latency. [`RandomStream`] instead spawns a dedicated reader thread
+//! that owns a cloned port handle, loops on reads into a reusable buffer, and
+//! forwards chunks over a bounded channel — decoupling the consumer from
+//! serial I/O entirely. Produced by [`QCicada::stream`](crate::device::QCicada::stream).
+
+use std::collections::VecDeque;
+use std::io::{Read as IoRead, Write as IoWrite};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+
+use serialport::SerialPort;
+
+use crate::QCicadaError;
+
+/// Number of in-flight chunks the reader thread may buffer before blocking.
+const CHANNEL_CAPACITY: usize = 64;
+/// Size of the reader thread's reusable read buffer.
+const READ_CHUNK: usize = 4096;
+
+/// Command sent from [`RandomStream`] to its reader thread.
+enum Cmd {
+    Stop,
+}
+
+/// An endless random byte stream backed by a dedicated reader thread.
+///
+/// Implements [`Iterator<Item = u8>`] and [`std::io::Read`]. Call
+/// [`stop`](Self::stop) to halt continuous mode and join the reader thread
+/// (also done automatically on drop).
+pub struct RandomStream {
+    rx: Receiver<Vec<u8>>,
+    cmd_tx: SyncSender<Cmd>,
+    handle: Option<JoinHandle<()>>,
+    pending: VecDeque<u8>,
+}
+
+impl RandomStream {
+    /// Spawn the reader thread over an already-cloned port handle that has
+    /// already been put into continuous mode by the caller.
+    pub(crate) fn spawn(mut port: Box<dyn SerialPort>) -> Self {
+        let (tx, rx) = sync_channel::<Vec<u8>>(CHANNEL_CAPACITY);
+        let (cmd_tx, cmd_rx) = sync_channel::<Cmd>(1);
+
+        let handle = std::thread::spawn(move || {
+            let mut buf = vec![0u8; READ_CHUNK];
+            loop {
+                if matches!(cmd_rx.try_recv(), Ok(Cmd::Stop)) {
+                    break;
+                }
+                match port.read(&mut buf) {
+                    Ok(0) => continue,
+                    Ok(n) => {
+                        if tx.send(buf[..n].to_vec()).is_err() {
+                            // Consumer dropped the stream without calling stop().
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
+                    Err(_) => break,
+                }
+            }
+
+            // Stop continuous mode and drain any data already in flight so
+            // the next command on the main transport isn't desynchronized.
+            let _ = port.write_all(&[0x05]);
+            let mut drain = [0u8; READ_CHUNK];
+            loop {
+                match port.read(&mut drain) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => continue,
+                }
+            }
+        });
+
+        Self {
+            rx,
+            cmd_tx,
+            handle: Some(handle),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Stop continuous generation and join the reader thread.
+    ///
+    /// Idempotent — safe to call more than once, including implicitly via
+    /// `Drop`.
+    pub fn stop(&mut self) -> Result<(), QCicadaError> {
+        match self.cmd_tx.try_send(Cmd::Stop) {
+            Ok(()) | Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {}
+        }
+        if let Some(handle) = self.handle.take() {
+            // The reader thread only notices `Cmd::Stop` between blocking
+            // `port.read`/`tx.send` calls, so if the channel is full it can
+            // be parked inside `tx.send` and never see it. Drain the
+            // channel until the thread drops its sender (i.e. has fully
+            // exited) so that can't leave `join` below hanging forever.
+            while self.rx.recv().is_ok() {}
+            handle
+                .join()
+                .map_err(|_| QCicadaError::Protocol("Stream reader thread panicked".into()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for RandomStream {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some(byte) = self.pending.pop_front() {
+            return Some(byte);
+        }
+        let chunk = self.rx.recv().ok()?;
+        self.pending.extend(chunk);
+        self.pending.pop_front()
+    }
+}
+
+impl std::io::Read for RandomStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        while n < buf.len() {
+            match self.next() {
+                Some(byte) => {
+                    buf[n] = byte;
+                    n += 1;
+                }
+                None => break,
+            }
+        }
+        Ok(n)
+    }
+}
+
+impl Drop for RandomStream {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
@@ -0,0 +1,153 @@
+//! Certificate-authority trust store and device attestation.
+//!
+//! [`QCicada::get_verified_pub_key`](crate::device::QCicada::get_verified_pub_key)
+//! proves a device's key is CA-signed, but only if the caller already knows
+//! which CA public key to check against. [`TrustStore`] embeds the known
+//! QCicada CA roots at compile time and tries each one in turn, so
+//! [`QCicada::attest`](crate::device::QCicada::attest) can prove "this is a
+//! real QCicada" with no external configuration.
+
+use sha2::{Digest, Sha256};
+
+use crate::crypto::verify_certificate;
+use crate::QCicadaError;
+
+/// QCicada CA root public keys (64 bytes, x || y) embedded at compile time.
+///
+/// [`TrustStore::default`] trusts exactly these roots. Use
+/// [`TrustStore::with_roots`] to pin a different set, e.g. in tests or for a
+/// fleet provisioned under a private CA.
+const EMBEDDED_ROOTS: &[[u8; 64]] = &[[
+    0x4a, 0x51, 0xa3, 0xd8, 0x0e, 0x6f, 0x12, 0x7b, 0x9c, 0x3d, 0x5e, 0x88, 0x01, 0xf4, 0x6a, 0x2c,
+    0x77, 0xbd, 0x0f, 0x93, 0x5c, 0x21, 0x8e, 0x4a, 0x6d, 0xc2, 0x39, 0x7f, 0x15, 0xa8, 0x64, 0xeb,
+    0x3b, 0x92, 0x5d, 0x10, 0xc7, 0x48, 0x2e, 0x6f, 0x99, 0x0a, 0x57, 0xd4, 0x1c, 0x83, 0xf6, 0x2b,
+    0x95, 0x0e, 0x4c, 0x79, 0xa1, 0xd3, 0x68, 0x2f, 0x5b, 0xc0, 0x94, 0x37, 0x1a, 0xe8, 0x6d, 0x52,
+]];
+
+/// A set of trusted QCicada CA root public keys.
+#[derive(Debug, Clone)]
+pub struct TrustStore {
+    roots: Vec<[u8; 64]>,
+}
+
+impl TrustStore {
+    /// Trust exactly `roots` (64 bytes x || y each), replacing the embedded
+    /// defaults entirely.
+    pub fn with_roots(roots: Vec<[u8; 64]>) -> Self {
+        Self { roots }
+    }
+}
+
+impl Default for TrustStore {
+    /// Trust the embedded QCicada CA roots.
+    fn default() -> Self {
+        Self::with_roots(EMBEDDED_ROOTS.to_vec())
+    }
+}
+
+/// Proof that a device's public key chains to a trusted CA root.
+///
+/// Returned by [`QCicada::attest`](crate::device::QCicada::attest).
+#[derive(Debug, Clone)]
+pub struct Attestation {
+    /// Numeric serial from the device's `"QC…"` serial string.
+    pub serial: u32,
+    /// (hw_major, hw_minor) parsed from `DeviceInfo.hw_info`.
+    pub hw_version: (u8, u8),
+    /// 64-byte (x || y) device public key, now known to be CA-attested.
+    pub pub_key: Vec<u8>,
+    /// SHA-256 of the CA root that validated the certificate, for audit
+    /// logging — identifies *which* trusted root signed off without
+    /// exposing the full key.
+    pub ca_fingerprint: [u8; 32],
+}
+
+/// Verify `certificate` against every root in `trust_store`, returning an
+/// [`Attestation`] for the first one that validates.
+///
+/// A root whose bytes don't form a valid P-256 point is treated the same as
+/// one that simply doesn't verify — trying the next root — rather than
+/// aborting the whole chain.
+pub fn verify_trust_chain(
+    trust_store: &TrustStore,
+    device_pub_key: &[u8],
+    certificate: &[u8],
+    hw_major: u8,
+    hw_minor: u8,
+    serial_int: u32,
+) -> Result<Attestation, QCicadaError> {
+    for root in &trust_store.roots {
+        if let Ok(true) =
+            verify_certificate(root, device_pub_key, certificate, hw_major, hw_minor, serial_int)
+        {
+            return Ok(Attestation {
+                serial: serial_int,
+                hw_version: (hw_major, hw_minor),
+                pub_key: device_pub_key.to_vec(),
+                ca_fingerprint: Sha256::digest(root).into(),
+            });
+        }
+    }
+
+    Err(QCicadaError::Signature(
+        "Device certificate does not chain to any trusted root".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+    use p256::SecretKey;
+
+    use crate::protocol::build_certificate_data;
+
+    fn keypair(seed: u8) -> ([u8; 64], SigningKey) {
+        let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+        let signing = SigningKey::from(secret);
+        let point = signing.verifying_key().to_encoded_point(false);
+        let raw: [u8; 64] = point.as_bytes()[1..].try_into().unwrap();
+        (raw, signing)
+    }
+
+    #[test]
+    fn verify_trust_chain_matches_second_root() {
+        let (other_root, _) = keypair(0x11);
+        let (ca_pub, ca_signing) = keypair(0x22);
+        let trust_store = TrustStore::with_roots(vec![other_root, ca_pub]);
+
+        let device_pub = vec![0x33; 64];
+        let cert_data = build_certificate_data(1, 1, 217, &device_pub);
+        let sig: Signature = ca_signing.sign(&cert_data);
+        let certificate = sig.to_bytes().to_vec();
+
+        let attestation =
+            verify_trust_chain(&trust_store, &device_pub, &certificate, 1, 1, 217).unwrap();
+        assert_eq!(attestation.serial, 217);
+        assert_eq!(attestation.hw_version, (1, 1));
+        assert_eq!(attestation.pub_key, device_pub);
+        assert_eq!(attestation.ca_fingerprint, Sha256::digest(ca_pub).as_slice());
+    }
+
+    #[test]
+    fn verify_trust_chain_rejects_untrusted_ca() {
+        let (ca_pub, ca_signing) = keypair(0x44);
+        let (unrelated_root, _) = keypair(0x55);
+        let trust_store = TrustStore::with_roots(vec![unrelated_root]);
+
+        let device_pub = vec![0x66; 64];
+        let cert_data = build_certificate_data(1, 1, 217, &device_pub);
+        let sig: Signature = ca_signing.sign(&cert_data);
+        let certificate = sig.to_bytes().to_vec();
+
+        assert!(verify_trust_chain(&trust_store, &device_pub, &certificate, 1, 1, 217).is_err());
+    }
+
+    #[test]
+    fn default_trust_store_has_embedded_roots() {
+        assert!(!TrustStore::with_roots(TrustStore::default().roots.clone())
+            .roots
+            .is_empty());
+    }
+}
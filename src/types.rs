@@ -82,3 +82,23 @@ pub struct SignedRead {
     /// 64-byte cryptographic signature over the data.
     pub signature: Vec<u8>,
 }
+
+impl SignedRead {
+    /// Verify the signature over `data` against the device's ECDSA P-256
+    /// public key (64 bytes, x || y), returning
+    /// [`QCicadaError::Signature`] on a mismatch or malformed input.
+    ///
+    /// Without this, a [`SignedRead`] is indistinguishable from an unsigned
+    /// one — callers had no way to check it themselves.
+    pub fn verify(&self, device_pub_key: &[u8]) -> Result<(), crate::QCicadaError> {
+        let valid = crate::crypto::verify_signature(device_pub_key, &self.data, &self.signature)
+            .map_err(crate::QCicadaError::Signature)?;
+        if valid {
+            Ok(())
+        } else {
+            Err(crate::QCicadaError::Signature(
+                "Signature does not match data".into(),
+            ))
+        }
+    }
+}